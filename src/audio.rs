@@ -2,8 +2,26 @@ use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
 use rodio::source::SineWave;
 use std::fs::File;
 use std::io::BufReader;
+use std::path::Path;
 use std::time::Duration;
 
+const BGM_PATH: &str = "assets/steps.mp3";
+const STEP_PATH: &str = "assets/pasos.mp3";
+const WIN_PATH: &str = "assets/victoria.mp3";
+const PICKUP_PATH: &str = "assets/pickup.mp3";
+
+// Música de fondo (loop); si falta el archivo, un tono suave.
+fn append_bgm(sink: &Sink) {
+    if let Ok(file) = File::open(BGM_PATH)
+        && let Ok(src) = rodio::Decoder::new(BufReader::new(file))
+    {
+        sink.append(src.repeat_infinite());
+        return;
+    }
+    let src = SineWave::new(220.0).amplify(0.02).repeat_infinite();
+    sink.append(src);
+}
+
 pub struct Audio {
     _stream: OutputStream,
     handle: OutputStreamHandle,
@@ -20,14 +38,7 @@ impl Audio {
         let (stream, handle) = OutputStream::try_default().expect("No hay dispositivo de audio");
         let bgm_sink = Sink::try_new(&handle).unwrap();
 
-        // Música de fondo (loop)
-        if let Ok(file) = File::open("assets/steps.mp3") {
-            let src = rodio::Decoder::new(BufReader::new(file)).unwrap();
-            bgm_sink.append(src.repeat_infinite());
-        } else {
-            let src = SineWave::new(220.0).amplify(0.02).repeat_infinite();
-            bgm_sink.append(src);
-        }
+        append_bgm(&bgm_sink);
         bgm_sink.set_volume(0.7); // volumen inicial
         bgm_sink.play();
 
@@ -52,15 +63,38 @@ impl Audio {
         }
     }
 
+    /// Volúmenes base (0..1) desde los ajustes; respeta el mute.
+    pub fn set_volumes(&mut self, music: f32, sfx: f32) {
+        self.bgm_volume = music;
+        self.sfx_volume = sfx;
+        self.set_muted(self.muted);
+    }
+
     pub fn toggle_muted(&mut self) -> bool {
         let new_state = !self.muted;
         self.set_muted(new_state);
         new_state
     }
 
+    /// Hot-reload: la música se reemplaza en caliente; los SFX se abren en
+    /// cada reproducción, así que basta con reconocer el archivo.
+    pub fn reload(&mut self, path: &Path) -> bool {
+        if path == Path::new(BGM_PATH) {
+            if let Ok(sink) = Sink::try_new(&self.handle) {
+                append_bgm(&sink);
+                sink.set_volume(if self.muted { 0.0 } else { self.bgm_volume });
+                sink.play();
+                self.bgm_sink.stop();
+                self.bgm_sink = sink;
+            }
+            return true;
+        }
+        [STEP_PATH, WIN_PATH, PICKUP_PATH].iter().any(|p| path == Path::new(p))
+    }
+
     pub fn play_step(&self) {
         if let Ok(sink) = Sink::try_new(&self.handle) {
-            if let Ok(file) = File::open("assets/pasos.mp3") {
+            if let Ok(file) = File::open(STEP_PATH) {
                 let src = rodio::Decoder::new(BufReader::new(file)).unwrap();
                 sink.append(src);
             } else {
@@ -74,10 +108,26 @@ impl Audio {
         }
     }
 
+    /// "Ding" al recoger un objeto (o abrir una puerta con llave).
+    pub fn play_pickup(&self) {
+        if let Ok(sink) = Sink::try_new(&self.handle) {
+            if let Ok(file) = File::open(PICKUP_PATH) {
+                let src = rodio::Decoder::new(BufReader::new(file)).unwrap();
+                sink.append(src);
+            } else {
+                let a = SineWave::new(880.0).amplify(0.05).take_duration(Duration::from_millis(70));
+                let b = SineWave::new(1320.0).amplify(0.05).take_duration(Duration::from_millis(110));
+                sink.append(a); sink.append(b);
+            }
+            sink.set_volume(if self.muted { 0.0 } else { self.sfx_volume });
+            sink.detach();
+        }
+    }
+
     pub fn play_win(&mut self) {
         if let Some(s) = self.win_sink.take() { s.stop(); }
         if let Ok(sink) = Sink::try_new(&self.handle) {
-            if let Ok(file) = File::open("assets/victoria.mp3") {
+            if let Ok(file) = File::open(WIN_PATH) {
                 let src = rodio::Decoder::new(BufReader::new(file)).unwrap();
                 sink.append(src);
             } else {
@@ -0,0 +1,480 @@
+use crate::constants::{rgb, alpha_blend, WIDTH, HEIGHT};
+use crate::hud::{draw_text, draw_text_centered, put_pixel};
+use crate::world::rand_range;
+use std::collections::VecDeque;
+
+// ===================== Tipos, movimientos y especies =====================
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PokeType { Normal, Fire, Water, Grass }
+
+impl PokeType {
+    /// Multiplicador de daño de un ataque de tipo `self` contra `def`.
+    /// Fuego > Planta > Agua > Fuego; cada uno resiste su propio tipo.
+    pub fn effectiveness(self, def: PokeType) -> f64 {
+        use PokeType::*;
+        match (self, def) {
+            (Fire, Grass) | (Grass, Water) | (Water, Fire) => 2.0,
+            (Fire, Water) | (Water, Grass) | (Grass, Fire) => 0.5,
+            (Fire, Fire) | (Water, Water) | (Grass, Grass) => 0.5,
+            _ => 1.0,
+        }
+    }
+
+    pub fn color(self) -> u32 {
+        match self {
+            PokeType::Normal => rgb(200, 190, 170),
+            PokeType::Fire => rgb(240, 110, 40),
+            PokeType::Water => rgb(70, 140, 240),
+            PokeType::Grass => rgb(90, 200, 80),
+        }
+    }
+}
+
+pub struct Move { pub name: &'static str, pub ty: PokeType, pub power: i32 }
+
+pub const PLACAJE: Move = Move { name: "PLACAJE", ty: PokeType::Normal, power: 35 };
+pub const ARANAZO: Move = Move { name: "ARANAZO", ty: PokeType::Normal, power: 40 };
+pub const ASCUAS: Move = Move { name: "ASCUAS", ty: PokeType::Fire, power: 40 };
+pub const LANZALLAMAS: Move = Move { name: "LANZALLAMAS", ty: PokeType::Fire, power: 70 };
+pub const PISTOLA_AGUA: Move = Move { name: "PISTOLA AGUA", ty: PokeType::Water, power: 40 };
+pub const BURBUJA: Move = Move { name: "RAYO BURBUJA", ty: PokeType::Water, power: 65 };
+pub const LATIGO_CEPA: Move = Move { name: "LATIGO CEPA", ty: PokeType::Grass, power: 45 };
+pub const HOJA_AFILADA: Move = Move { name: "HOJA AFILADA", ty: PokeType::Grass, power: 65 };
+
+pub struct Species {
+    pub name: &'static str,
+    pub ty: PokeType,
+    pub base_hp: i32,
+    pub atk: i32,
+    pub def: i32,
+    pub moves: &'static [&'static Move],
+}
+
+pub const CHARMANDER: Species = Species { name: "CHARMANDER", ty: PokeType::Fire, base_hp: 39, atk: 52, def: 43, moves: &[&ARANAZO, &ASCUAS, &LANZALLAMAS] };
+pub const VULPIX: Species = Species { name: "VULPIX", ty: PokeType::Fire, base_hp: 38, atk: 41, def: 40, moves: &[&PLACAJE, &ASCUAS] };
+pub const SQUIRTLE: Species = Species { name: "SQUIRTLE", ty: PokeType::Water, base_hp: 44, atk: 48, def: 65, moves: &[&PLACAJE, &PISTOLA_AGUA, &BURBUJA] };
+pub const PSYDUCK: Species = Species { name: "PSYDUCK", ty: PokeType::Water, base_hp: 50, atk: 52, def: 48, moves: &[&ARANAZO, &PISTOLA_AGUA] };
+pub const BULBASAUR: Species = Species { name: "BULBASAUR", ty: PokeType::Grass, base_hp: 45, atk: 49, def: 49, moves: &[&PLACAJE, &LATIGO_CEPA, &HOJA_AFILADA] };
+pub const ODDISH: Species = Species { name: "ODDISH", ty: PokeType::Grass, base_hp: 45, atk: 50, def: 55, moves: &[&PLACAJE, &LATIGO_CEPA] };
+pub const RATTATA: Species = Species { name: "RATTATA", ty: PokeType::Normal, base_hp: 30, atk: 56, def: 35, moves: &[&PLACAJE, &ARANAZO] };
+
+const ALL_SPECIES: [&Species; 7] = [&CHARMANDER, &VULPIX, &SQUIRTLE, &PSYDUCK, &BULBASAUR, &ODDISH, &RATTATA];
+
+/// Para cargar partidas: la especie se guarda por nombre.
+pub fn species_by_name(name: &str) -> Option<&'static Species> {
+    ALL_SPECIES.iter().copied().find(|s| s.name == name)
+}
+
+#[derive(Clone)]
+pub struct Creature {
+    pub species: &'static Species,
+    pub level: i32,
+    pub hp: i32,
+    pub max_hp: i32,
+}
+
+impl Creature {
+    pub fn new(species: &'static Species, level: i32) -> Self {
+        let max_hp = species.base_hp * level / 25 + level + 12;
+        Self { species, level, hp: max_hp, max_hp }
+    }
+    pub fn name(&self) -> &'static str { self.species.name }
+    pub fn fainted(&self) -> bool { self.hp <= 0 }
+    fn atk(&self) -> i32 { self.species.atk * self.level / 25 + 5 }
+    fn def(&self) -> i32 { self.species.def * self.level / 25 + 5 }
+}
+
+/// Daño de un ataque lanzado fuera de combate (el proyectil del pokémon activo).
+pub fn field_damage(atk: &Creature, def: &Creature) -> i32 {
+    let base = ((2 * atk.level / 5 + 2) * 40 * atk.atk() / def.def().max(1)) / 50 + 2;
+    ((base as f64) * atk.species.ty.effectiveness(def.species.ty)).max(1.0) as i32
+}
+
+/// Probabilidad de captura con una pokébola: casi nula a vida llena, alta si está debilitado.
+pub fn capture_chance(c: &Creature) -> f64 {
+    let ratio = c.hp.max(0) as f64 / c.max_hp.max(1) as f64;
+    (0.9 - 0.8 * ratio).clamp(0.05, 0.9)
+}
+
+// ===================== Equipo =====================
+
+pub const PARTY_MAX: usize = 6;
+
+#[derive(Clone)]
+pub struct Party {
+    pub members: Vec<Creature>,
+    pub active: usize,
+}
+
+impl Party {
+    pub fn starter() -> Self {
+        Self { members: vec![Creature::new(&CHARMANDER, 8)], active: 0 }
+    }
+    pub fn active(&self) -> &Creature { &self.members[self.active] }
+    /// Centro pokémon: todos a vida llena.
+    pub fn heal_all(&mut self) {
+        for c in &mut self.members { c.hp = c.max_hp; }
+    }
+    /// Suma un capturado; devuelve false si el equipo ya está lleno.
+    pub fn add(&mut self, c: Creature) -> bool {
+        if self.members.len() >= PARTY_MAX { return false; }
+        self.members.push(c);
+        true
+    }
+    fn next_alive(&self) -> Option<usize> { self.members.iter().position(|c| !c.fainted()) }
+}
+
+// ===================== Encuentros =====================
+
+/// Pokémon salvajes posibles según el elemento del gimnasio.
+fn wild_pool(element: PokeType) -> &'static [&'static Species] {
+    match element {
+        PokeType::Fire => &[&VULPIX, &VULPIX, &ODDISH, &RATTATA],
+        PokeType::Water => &[&PSYDUCK, &PSYDUCK, &ODDISH, &RATTATA],
+        PokeType::Grass => &[&ODDISH, &ODDISH, &RATTATA],
+        PokeType::Normal => &[&RATTATA],
+    }
+}
+
+pub fn wild_creature(element: PokeType, rng: &mut u64) -> Creature {
+    let pool = wild_pool(element);
+    let sp = pool[rand_range(rng, pool.len() as i32) as usize];
+    Creature::new(sp, 4 + rand_range(rng, 4))
+}
+
+/// Equipo de un entrenador del gimnasio: 1–2 del elemento del nivel.
+pub fn trainer_party(element: PokeType, rng: &mut u64) -> Vec<Creature> {
+    let (a, b): (&'static Species, &'static Species) = match element {
+        PokeType::Fire => (&VULPIX, &CHARMANDER),
+        PokeType::Water => (&PSYDUCK, &SQUIRTLE),
+        PokeType::Grass => (&ODDISH, &BULBASAUR),
+        PokeType::Normal => (&RATTATA, &RATTATA),
+    };
+    let mut foes = vec![Creature::new(a, 6 + rand_range(rng, 3))];
+    if rand_range(rng, 2) == 0 { foes.push(Creature::new(b, 7)); }
+    foes
+}
+
+/// Equipo del líder: fijo y más fuerte, cierra el gimnasio.
+pub fn leader_party(element: PokeType) -> Vec<Creature> {
+    match element {
+        PokeType::Fire => vec![Creature::new(&VULPIX, 9), Creature::new(&CHARMANDER, 10)],
+        PokeType::Water => vec![Creature::new(&PSYDUCK, 9), Creature::new(&SQUIRTLE, 10)],
+        PokeType::Grass => vec![Creature::new(&ODDISH, 9), Creature::new(&BULBASAUR, 10)],
+        PokeType::Normal => vec![Creature::new(&RATTATA, 10)],
+    }
+}
+
+// ===================== Combate =====================
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum BattleKind { Wild, Trainer, Leader(&'static str) }
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Outcome { Won, Lost, Fled }
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum BattleInput { Up, Down, Confirm, Back }
+
+#[derive(Copy, Clone, PartialEq)]
+enum Menu { Main, Moves, Party }
+
+enum Action { Attack(usize), Switch(usize), Run }
+
+const MAIN_OPTIONS: [&str; 3] = ["LUCHAR", "POKEMON", "HUIR"];
+const MSG_TIME: f64 = 1.4; // s por mensaje si no se apura con confirmar
+
+pub struct Battle {
+    pub kind: BattleKind,
+    pub foes: Vec<Creature>,
+    foe_idx: usize,
+    menu: Menu,
+    cursor: usize,
+    queue: VecDeque<String>,
+    msg_timer: f64,
+    outcome: Option<Outcome>,
+    rng: u64,
+}
+
+impl Battle {
+    pub fn new(kind: BattleKind, foes: Vec<Creature>, seed: u64) -> Self {
+        let mut b = Self {
+            kind, foes, foe_idx: 0, menu: Menu::Main, cursor: 0,
+            queue: VecDeque::new(), msg_timer: 0.0, outcome: None, rng: seed | 1,
+        };
+        let intro = match kind {
+            BattleKind::Wild => format!("UN {} SALVAJE APARECIO", b.foe().name()),
+            BattleKind::Trainer => format!("EL ENTRENADOR SACO A {}", b.foe().name()),
+            BattleKind::Leader(name) => format!("EL LIDER {name} TE DESAFIA"),
+        };
+        b.say(intro);
+        if let BattleKind::Leader(name) = kind { b.say(format!("{name} SACO A {}", b.foe().name())); }
+        b
+    }
+
+    pub fn foe(&self) -> &Creature { &self.foes[self.foe_idx] }
+
+    fn say(&mut self, msg: String) {
+        if self.queue.is_empty() { self.msg_timer = MSG_TIME; }
+        self.queue.push_back(msg);
+    }
+
+    /// Avanza los mensajes; devuelve el resultado cuando el combate terminó
+    /// y ya se leyó todo.
+    pub fn update(&mut self, dt: f64) -> Option<Outcome> {
+        if !self.queue.is_empty() {
+            self.msg_timer -= dt;
+            if self.msg_timer <= 0.0 { self.next_message(); }
+            return None;
+        }
+        self.outcome
+    }
+
+    fn next_message(&mut self) {
+        self.queue.pop_front();
+        self.msg_timer = MSG_TIME;
+    }
+
+    pub fn input(&mut self, inp: BattleInput, party: &mut Party) {
+        if !self.queue.is_empty() {
+            if inp == BattleInput::Confirm { self.next_message(); }
+            return;
+        }
+        if self.outcome.is_some() { return; }
+
+        let n = match self.menu {
+            Menu::Main => MAIN_OPTIONS.len(),
+            Menu::Moves => party.active().species.moves.len(),
+            Menu::Party => party.members.len(),
+        };
+        match inp {
+            BattleInput::Up => self.cursor = (self.cursor + n - 1) % n,
+            BattleInput::Down => self.cursor = (self.cursor + 1) % n,
+            BattleInput::Back => { self.menu = Menu::Main; self.cursor = 0; }
+            BattleInput::Confirm => match self.menu {
+                Menu::Main => match self.cursor {
+                    0 => { self.menu = Menu::Moves; self.cursor = 0; }
+                    1 => { self.menu = Menu::Party; self.cursor = party.active; }
+                    _ => self.turn(Action::Run, party),
+                },
+                Menu::Moves => { let i = self.cursor; self.turn(Action::Attack(i), party); }
+                Menu::Party => {
+                    let i = self.cursor;
+                    if i != party.active && !party.members[i].fainted() { self.turn(Action::Switch(i), party); }
+                }
+            },
+        }
+    }
+
+    // Daño estilo clásico (simplificado): nivel, poder, atk/def, STAB, tipo y un poco de azar.
+    fn damage(&mut self, atk: &Creature, mv: &Move, def: &Creature) -> (i32, f64) {
+        let base = ((2 * atk.level / 5 + 2) * mv.power * atk.atk() / def.def().max(1)) / 50 + 2;
+        let stab = if mv.ty == atk.species.ty { 1.5 } else { 1.0 };
+        let eff = mv.ty.effectiveness(def.species.ty);
+        let roll = 0.85 + rand_range(&mut self.rng, 16) as f64 / 100.0;
+        (((base as f64) * stab * eff * roll).max(1.0) as i32, eff)
+    }
+
+    fn effectiveness_msg(&mut self, eff: f64) {
+        if eff > 1.0 { self.say("ES MUY EFICAZ".to_string()); }
+        else if eff < 1.0 { self.say("NO ES MUY EFICAZ".to_string()); }
+    }
+
+    fn turn(&mut self, action: Action, party: &mut Party) {
+        self.menu = Menu::Main;
+        self.cursor = 0;
+
+        // 1) Acción del jugador (siempre va primero)
+        match action {
+            Action::Run => {
+                if self.kind != BattleKind::Wild {
+                    self.say("NO PUEDES HUIR DE UN ENTRENADOR".to_string());
+                    return;
+                }
+                if rand_range(&mut self.rng, 4) != 0 {
+                    self.say("ESCAPASTE SIN PROBLEMAS".to_string());
+                    self.outcome = Some(Outcome::Fled);
+                    return;
+                }
+                self.say("NO PUDISTE ESCAPAR".to_string());
+            }
+            Action::Switch(i) => {
+                party.active = i;
+                self.say(format!("ADELANTE {}", party.active().name()));
+            }
+            Action::Attack(i) => {
+                let me = party.active().clone();
+                let mv = me.species.moves[i];
+                let foe = self.foe().clone();
+                let (dmg, eff) = self.damage(&me, mv, &foe);
+                self.say(format!("{} USO {}", me.name(), mv.name));
+                self.effectiveness_msg(eff);
+                let idx = self.foe_idx;
+                self.foes[idx].hp = (self.foes[idx].hp - dmg).max(0);
+                if self.foes[idx].fainted() {
+                    self.say(format!("{} SE DEBILITO", foe.name()));
+                    if self.foe_idx + 1 < self.foes.len() {
+                        self.foe_idx += 1;
+                        self.say(format!("EL ENTRENADOR SACO A {}", self.foe().name()));
+                    } else {
+                        self.say("GANASTE EL COMBATE".to_string());
+                        self.outcome = Some(Outcome::Won);
+                    }
+                    return;
+                }
+            }
+        }
+
+        // 2) Responde el rival con un movimiento al azar
+        let foe = self.foe().clone();
+        let mv = foe.species.moves[rand_range(&mut self.rng, foe.species.moves.len() as i32) as usize];
+        let me = party.active().clone();
+        let (dmg, eff) = self.damage(&foe, mv, &me);
+        self.say(format!("{} RIVAL USO {}", foe.name(), mv.name));
+        self.effectiveness_msg(eff);
+        let a = party.active;
+        party.members[a].hp = (party.members[a].hp - dmg).max(0);
+        if party.members[a].fainted() {
+            self.say(format!("{} SE DEBILITO", me.name()));
+            match party.next_alive() {
+                Some(j) => {
+                    party.active = j;
+                    self.say(format!("ADELANTE {}", party.active().name()));
+                }
+                None => {
+                    self.say("NO TE QUEDAN POKEMON".to_string());
+                    self.outcome = Some(Outcome::Lost);
+                }
+            }
+        }
+    }
+
+    // ===================== Dibujo =====================
+
+    pub fn draw(&self, buf: &mut [u32], party: &Party) {
+        // Fondo: cielo degradado + pasto
+        let horizon = HEIGHT * 11 / 20;
+        for y in 0..HEIGHT {
+            let c = if y < horizon {
+                let t = y as f64 / horizon as f64;
+                rgb((120.0 + 80.0 * t) as u8, (170.0 + 50.0 * t) as u8, (230.0 + 10.0 * t) as u8)
+            } else {
+                let t = (y - horizon) as f64 / (HEIGHT - horizon) as f64;
+                rgb((110.0 - 40.0 * t) as u8, (170.0 - 50.0 * t) as u8, (90.0 - 30.0 * t) as u8)
+            };
+            for x in 0..WIDTH { buf[y * WIDTH + x] = c; }
+        }
+
+        // Plataformas y criaturas
+        let foe = self.foe();
+        let (fx, fy) = (WIDTH * 3 / 4, HEIGHT * 2 / 7);
+        let (mx, my) = (WIDTH / 4, HEIGHT * 8 / 15);
+        draw_platform(buf, fx, fy + 55, 110);
+        draw_platform(buf, mx, my + 70, 140);
+        if !foe.fainted() { draw_portrait(buf, fx, fy, 50, foe.species.ty, false); }
+        if !party.active().fainted() { draw_portrait(buf, mx, my, 65, party.active().species.ty, true); }
+
+        // Cajas de info
+        draw_info(buf, 30, 40, foe);
+        draw_info(buf, WIDTH - 330, HEIGHT * 9 / 20, party.active());
+
+        // Panel inferior: mensaje o menú
+        let py0 = HEIGHT - 130;
+        panel(buf, 10, py0, WIDTH - 20, 120);
+        if let Some(msg) = self.queue.front() {
+            draw_text(buf, msg, 30, py0 as i32 + 45, 3, rgb(255, 255, 255));
+            return;
+        }
+        if self.outcome.is_some() { return; }
+
+        let items: Vec<(String, Option<u32>)> = match self.menu {
+            Menu::Main => MAIN_OPTIONS.iter().map(|s| (s.to_string(), None)).collect(),
+            Menu::Moves => party.active().species.moves.iter()
+                .map(|m| (m.name.to_string(), Some(m.ty.color()))).collect(),
+            Menu::Party => party.members.iter()
+                .map(|c| (c.name().to_string(), Some(if c.fainted() { rgb(90, 90, 90) } else { c.species.ty.color() })))
+                .collect(),
+        };
+        // Columnas de 3 filas
+        for (i, (label, col)) in items.iter().enumerate() {
+            let x = 40 + (i / 3) as i32 * 360;
+            let y = py0 as i32 + 14 + (i % 3) as i32 * 34;
+            let sel = i == self.cursor;
+            if sel { draw_text(buf, ">", x - 22, y, 3, rgb(255, 230, 120)); }
+            if let Some(c) = col {
+                for yy in 0..14 { for xx in 0..14 { put_pixel(buf, x as usize + xx, y as usize + 3 + yy, *c); } }
+            }
+            let tx = if col.is_some() { x + 22 } else { x };
+            draw_text(buf, label, tx, y, 3, if sel { rgb(255, 230, 120) } else { rgb(240, 240, 240) });
+        }
+        let hint = match self.menu {
+            Menu::Main => "FLECHAS ELEGIR  ESPACIO CONFIRMAR",
+            _ => "ESPACIO CONFIRMAR  RETROCESO VOLVER",
+        };
+        draw_text_centered(buf, hint, HEIGHT - 22, 2, rgb(170, 170, 190));
+    }
+}
+
+fn panel(buf: &mut [u32], x0: usize, y0: usize, w: usize, h: usize) {
+    for y in y0..(y0 + h).min(HEIGHT) {
+        for x in x0..(x0 + w).min(WIDTH) {
+            let edge = y < y0 + 3 || y >= y0 + h - 3 || x < x0 + 3 || x >= x0 + w - 3;
+            let bg = buf[y * WIDTH + x];
+            buf[y * WIDTH + x] = if edge { rgb(240, 240, 240) } else { alpha_blend(bg, rgb(20, 24, 40), 220) };
+        }
+    }
+}
+
+fn draw_info(buf: &mut [u32], x0: usize, y0: usize, c: &Creature) {
+    panel(buf, x0, y0, 300, 64);
+    draw_text(buf, c.name(), x0 as i32 + 14, y0 as i32 + 12, 2, rgb(255, 255, 255));
+    // Barra de PS
+    let (bx, by, bw, bh) = (x0 + 14, y0 + 38, 272usize, 10usize);
+    let pct = c.hp.max(0) as f64 / c.max_hp.max(1) as f64;
+    let col = if pct > 0.5 { rgb(70, 220, 90) } else if pct > 0.2 { rgb(255, 200, 50) } else { rgb(230, 60, 60) };
+    for y in 0..bh {
+        for x in 0..bw {
+            let filled = (x as f64) < pct * bw as f64;
+            put_pixel(buf, bx + x, by + y, if filled { col } else { rgb(50, 50, 60) });
+        }
+    }
+    // Marquitas del tipo
+    for yy in 0..8 { for xx in 0..8 { put_pixel(buf, x0 + 280 + xx, y0 + 14 + yy, c.species.ty.color()); } }
+}
+
+fn draw_platform(buf: &mut [u32], cx: usize, cy: usize, rx: usize) {
+    let ry = rx / 4;
+    for y in cy.saturating_sub(ry)..(cy + ry).min(HEIGHT) {
+        for x in cx.saturating_sub(rx)..(cx + rx).min(WIDTH) {
+            let fx = (x as f64 - cx as f64) / rx as f64;
+            let fy = (y as f64 - cy as f64) / ry as f64;
+            if fx * fx + fy * fy <= 1.0 {
+                let bg = buf[y * WIDTH + x];
+                buf[y * WIDTH + x] = alpha_blend(bg, rgb(60, 90, 50), 160);
+            }
+        }
+    }
+}
+
+// Criatura genérica: cuerpo del color del tipo; de espaldas no se le ven los ojos.
+fn draw_portrait(buf: &mut [u32], cx: usize, cy: usize, r: usize, ty: PokeType, back: bool) {
+    let base = ty.color();
+    let r = r as f64;
+    for y in 0..(2.4 * r) as usize {
+        for x in 0..(2.4 * r) as usize {
+            let fx = (x as f64 - 1.2 * r) / r;
+            let fy = (y as f64 - 1.2 * r) / r;
+            if fx * fx + fy * fy > 1.0 { continue; }
+            let light = (1.0 - 0.35 * (fx + fy + 1.0) / 2.0).clamp(0.5, 1.0);
+            let mut c = crate::constants::shade(base, light);
+            if !back {
+                for side in [-0.35, 0.35] {
+                    if (fx - side).powi(2) + (fy + 0.2).powi(2) <= 0.015 { c = rgb(20, 20, 30); }
+                }
+                if fx * fx * 3.0 + (fy - 0.25).powi(2) * 20.0 <= 0.08 { c = rgb(120, 20, 20); }
+            }
+            put_pixel(buf, (cx as f64 + fx * r) as usize, (cy as f64 + fy * r) as usize, c);
+        }
+    }
+}
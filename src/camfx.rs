@@ -0,0 +1,93 @@
+use std::f64::consts::PI;
+
+use crate::constants::TICK;
+use crate::player::Pose;
+use crate::render::Lens;
+use crate::sim::{STEP_EVERY, WALK_SPEED};
+
+const BOB_AMP: f64 = 0.035;    // cuánto baja el ojo al apoyar el pie (en altura de mundo)
+const FOV_KICK: f64 = 1.12;    // el plano se abre un 12% al correr
+const SHAKE_YAW: f64 = 0.06;   // radianes de temblor con trauma 1
+const SHAKE_PX: f64 = 22.0;    // píxeles de temblor del horizonte con trauma 1
+const SHAKE_ROLL: f64 = 0.05;
+const LEAN_STRAFE: f64 = 0.025; // roll al desplazarse de costado
+const LEAN_TURN: f64 = 0.008;   // roll por rad/s de giro
+const FALL_EYE: f64 = 0.32;    // al morir el ojo baja casi hasta el piso...
+const FALL_ROLL: f64 = 0.35;   // ...y la cámara cae de costado
+
+/// Efectos de cámara que no tocan la simulación: temblor por trauma al recibir
+/// daño, bamboleo al caminar (va con los pasos que suenan), FOV más abierto al
+/// correr y una inclinación suave al desplazarse o girar. Todo sale por la pose
+/// y la `Lens` que recibe el render, nada se corre después sobre los píxeles.
+pub struct CameraFx {
+    trauma: f64,    // 0..1; el temblor es trauma², así los golpes chicos casi no se notan
+    time: f64,
+    bob_phase: f64, // π por paso: en los múltiplos de π el pie está apoyado
+    bob: f64,       // 0..1, sube al caminar y baja al frenar
+    fov: f64,
+    roll: f64,
+    fall: f64,      // 0..1, caída al morir (ver `collapse`)
+}
+
+impl CameraFx {
+    pub fn new() -> Self {
+        Self { trauma: 0.0, time: 0.0, bob_phase: 0.0, bob: 0.0, fov: 1.0, roll: 0.0, fall: 0.0 }
+    }
+
+    pub fn add_trauma(&mut self, amount: f64) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    /// Sonó un paso: engancha la fase para que el ojo esté abajo justo ahí.
+    pub fn step(&mut self) {
+        self.bob_phase = (self.bob_phase / PI).round() * PI;
+    }
+
+    /// Cámara de muerte: 0 parado, 1 en el piso (hay que volverla a 0 después).
+    pub fn collapse(&mut self, k: f64) { self.fall = k.clamp(0.0, 1.0); }
+
+    /// Una vez por frame, con el movimiento del último tick (`from` → `to`).
+    pub fn update(&mut self, dt: f64, from: &Pose, to: &Pose) {
+        self.time += dt;
+        self.trauma = (self.trauma - 1.1 * dt).max(0.0);
+        let smooth = |rate: f64| 1.0 - (-rate * dt).exp();
+
+        let (vx, vy) = ((to.x - from.x) / TICK, (to.y - from.y) / TICK);
+        let mut speed = vx.hypot(vy);
+        if speed > WALK_SPEED * 4.0 { speed = 0.0; } // salto (partida cargada)
+
+        // Bamboleo: media vuelta de seno por paso, al ritmo de los pasos del sim
+        let moving = speed > 0.5;
+        self.bob += (moving as u8 as f64 - self.bob) * smooth(6.0);
+        if moving { self.bob_phase += PI * speed / (WALK_SPEED * STEP_EVERY) * dt; }
+
+        // Correr abre el campo visual (caminar en diagonal no llega)
+        let fov = if speed > WALK_SPEED * 1.5 { FOV_KICK } else { 1.0 };
+        self.fov += (fov - self.fov) * smooth(8.0);
+
+        // Se inclina hacia donde se desliza y hacia donde gira
+        let plane_len = to.plane_x.hypot(to.plane_y).max(1e-6);
+        let side = if speed > 0.0 { (vx * to.plane_x + vy * to.plane_y) / plane_len / WALK_SPEED } else { 0.0 };
+        let turn = (from.dir_x * to.dir_y - from.dir_y * to.dir_x).clamp(-1.0, 1.0).asin() / TICK;
+        let roll = -(side * LEAN_STRAFE + turn * LEAN_TURN);
+        self.roll += (roll.clamp(-0.08, 0.08) - self.roll) * smooth(10.0);
+    }
+
+    /// Cámara y lente para el render de este frame.
+    pub fn apply(&self, cam: Pose) -> (Pose, Lens) {
+        let shake = self.trauma * self.trauma;
+        // Ruido suave en -1..1 (dos senos que no se sincronizan)
+        let noise = |f: f64, o: f64| 0.6 * (self.time * f + o).sin() + 0.4 * (self.time * f * 2.31 + o * 1.7).sin();
+        let mut c = cam;
+        c.rotate(SHAKE_YAW * shake * noise(23.0, 0.0));
+        c.plane_x *= self.fov;
+        c.plane_y *= self.fov;
+        let fall = self.fall * (2.0 - self.fall); // rápido al principio, se asienta al final
+        let lens = Lens {
+            eye: -BOB_AMP * self.bob * (1.0 - self.bob_phase.sin().abs()) - FALL_EYE * fall,
+            horizon: SHAKE_PX * shake * noise(29.0, 1.3),
+            roll: self.roll + SHAKE_ROLL * shake * noise(17.0, 2.9) + FALL_ROLL * fall,
+        };
+        (c, lens)
+    }
+}
@@ -1,12 +1,19 @@
 pub const WIDTH: usize = 800;
 pub const HEIGHT: usize = 600;
 
+// Simulación a paso fijo: la lógica corre siempre a TICK_HZ, el render a lo que dé
+pub const TICK_HZ: f64 = 120.0;
+pub const TICK: f64 = 1.0 / TICK_HZ;
+pub const MAX_TICKS_PER_FRAME: u32 = 12; // si el frame tarda más, el juego va más lento en vez de congelarse
+
 pub const MAP_W: usize = 24;
 pub const MAP_H: usize = 24;
 
 // Tiles
 pub const TILE_EMPTY: i32 = 0;
 pub const TILE_WALL:  i32 = 1; // fuego
+pub const TILE_DOOR:  i32 = 2; // puerta cerrada (se abre con llave)
+pub const TILE_GRASS: i32 = 3; // hierba alta: encuentros salvajes
 pub const TILE_EXIT:  i32 = 9; // meta
 
 #[inline]
@@ -22,6 +29,14 @@ pub fn shade(color: u32, factor: f64) -> u32 {
     rgb((r * factor) as u8, (g * factor) as u8, (b * factor) as u8)
 }
 
+/// Multiplica canal a canal por `t` (0xFFFFFF = sin cambio).
+#[inline]
+pub fn tint(color: u32, t: u32) -> u32 {
+    if t == 0xFF_FFFF { return color; }
+    let ch = |sh: u32| (((color >> sh) & 0xFF) * ((t >> sh) & 0xFF) / 255) << sh;
+    ch(16) | ch(8) | ch(0)
+}
+
 #[inline]
 pub fn alpha_blend(bg: u32, fg: u32, a: u8) -> u32 {
     if a == 255 { return fg; }
@@ -0,0 +1,103 @@
+use std::time::Instant;
+
+use crate::constants::{rgb, WIDTH};
+use crate::hud::{draw_text, put_pixel};
+use crate::sim::Sim;
+
+const GRAPH_LEN: usize = 120; // frames en el gráfico (2 s a 60 fps)
+const GRAPH_MS: f64 = 50.0;   // tope del gráfico en ms
+const LINE: i32 = 12;         // alto de renglón (fuente a escala 1)
+
+/// Overlay de rendimiento (F3): FPS, gráfico de tiempos de frame, cuánto
+/// tarda cada fase del render y dónde está el jugador. Los tiempos se toman
+/// siempre (son un par de `Instant::now` por frame) y solo se dibujan si está visible.
+pub struct DebugOverlay {
+    pub visible: bool,
+    frames: [f32; GRAPH_LEN], // ms por frame, anillo
+    next: usize,
+    phases: Vec<(&'static str, f64)>,
+    mark: Instant,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self { visible: false, frames: [0.0; GRAPH_LEN], next: 0, phases: Vec::new(), mark: Instant::now() }
+    }
+
+    /// Al principio del frame: guarda cuánto duró el anterior y arranca las fases de cero.
+    pub fn begin(&mut self, dt: f64) {
+        self.frames[self.next] = (dt * 1000.0) as f32;
+        self.next = (self.next + 1) % GRAPH_LEN;
+        self.phases.clear();
+        self.mark = Instant::now();
+    }
+
+    /// Cierra una fase: el tiempo desde la fase anterior (o desde `begin`/`skip`).
+    pub fn lap(&mut self, name: &'static str) {
+        let now = Instant::now();
+        self.phases.push((name, (now - self.mark).as_secs_f64() * 1000.0));
+        self.mark = now;
+    }
+
+    /// Lo que pasó desde la última marca no cuenta (p.ej. la simulación antes del render).
+    pub fn skip(&mut self) { self.mark = Instant::now(); }
+
+    pub fn draw(&self, buf: &mut [u32], fps: u32, sim: &Sim) {
+        let (w, x0, y0) = (260usize, WIDTH - 270, 10usize);
+        let h = (6 + self.phases.len()) * LINE as usize + 80;
+        // Fondo oscuro semitransparente
+        for y in y0..y0 + h {
+            for x in x0..x0 + w {
+                let px = &mut buf[y * WIDTH + x];
+                *px = (*px >> 2) & 0x3F3F3F;
+            }
+        }
+
+        let white = rgb(235, 235, 235);
+        let (tx, mut y) = (x0 as i32 + 8, y0 as i32 + 8);
+
+        let avg = self.frames.iter().sum::<f32>() / GRAPH_LEN as f32;
+        let worst = self.frames.iter().cloned().fold(0.0, f32::max);
+        row(buf, &format!("FPS {fps}  frame {avg:.1} ms (peor {worst:.1})"), tx, &mut y, white);
+
+        // Fases del render con una barrita proporcional
+        let total: f64 = self.phases.iter().map(|(_, ms)| ms).sum();
+        for (name, ms) in &self.phases {
+            let bar = ((ms / total.max(0.001)) * 90.0) as usize;
+            for yy in 2..8 {
+                for xx in 0..bar { put_pixel(buf, x0 + 160 + xx, y as usize + yy, rgb(90, 170, 255)); }
+            }
+            row(buf, &format!("{name:<12} {ms:>5.2} ms"), tx, &mut y, white);
+        }
+
+        // Gráfico de frames: línea de 16.7 ms (60 fps) y de 33.3 ms (30 fps)
+        let (gx, gy, gh) = (x0 + 8, y as usize + 4, 60usize);
+        let to_y = |ms: f64| gy + gh - ((ms / GRAPH_MS).min(1.0) * gh as f64) as usize;
+        for (ms, c) in [(1000.0 / 60.0, rgb(60, 160, 60)), (1000.0 / 30.0, rgb(170, 120, 40))] {
+            for x in 0..GRAPH_LEN * 2 { put_pixel(buf, gx + x, to_y(ms), c); }
+        }
+        for i in 0..GRAPH_LEN {
+            let ms = self.frames[(self.next + i) % GRAPH_LEN] as f64;
+            let c = if ms > 1000.0 / 30.0 { rgb(255, 80, 60) } else if ms > 1000.0 / 55.0 { rgb(255, 210, 60) } else { rgb(120, 255, 120) };
+            for yy in to_y(ms)..=gy + gh {
+                put_pixel(buf, gx + i * 2, yy, c);
+                put_pixel(buf, gx + i * 2 + 1, yy, c);
+            }
+        }
+        y = (gy + gh + 8) as i32;
+
+        // Jugador y nivel
+        let p = &sim.player;
+        let (cx, cy) = (p.x as usize, p.y as usize);
+        row(buf, &format!("pos {:.2}, {:.2}  dir {:.0}°", p.x, p.y, p.dir_y.atan2(p.dir_x).to_degrees()), tx, &mut y, white);
+        row(buf, &format!("casilla {cx}, {cy}  tile {}", sim.map[cy][cx]), tx, &mut y, white);
+        row(buf, &format!("nivel {}  semilla {}", sim.level, sim.seed), tx, &mut y, white);
+        row(buf, &format!("t {:.2} s", sim.time), tx, &mut y, white);
+    }
+}
+
+// Un renglón de texto chico y baja al siguiente.
+fn row(buf: &mut [u32], s: &str, x: i32, y: &mut i32, color: u32) {
+    draw_text(buf, s, x, *y, 1, color);
+    *y += LINE;
+}
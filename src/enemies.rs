@@ -0,0 +1,356 @@
+use crate::constants::rgb;
+use crate::battle::{wild_creature, Creature, PokeType};
+use crate::player::Player;
+use crate::sprites::{AnimatedSprite, Animation, Fallback, SpriteFrame, SpriteSheet, VIEW_ANGLES};
+use crate::world::{find_path, is_passable, line_of_sight, rand_range, reachable, WorldMap};
+use std::rc::Rc;
+
+const SIGHT_RANGE: f64 = 7.0;     // celdas
+const LOSE_SIGHT_AFTER: f64 = 3.0; // s sin verte antes de volver a patrullar
+const CONTACT_RANGE: f64 = 0.55;
+const CONTACT_DAMAGE: i32 = 8;
+const PATROL_SPEED: f64 = 1.2;
+const CHASE_SPEED: f64 = 2.3;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum EnemyState { Patrol, Chase }
+
+/// Salvajes pegan por contacto; los entrenadores, al tocarte, inician un combate.
+/// El líder no se mueve: espera frente a la meta.
+#[derive(Copy, Clone, PartialEq)]
+pub enum EnemyKind { Wild, Trainer, Leader }
+
+impl EnemyKind {
+    pub fn id(self) -> &'static str {
+        match self { EnemyKind::Wild => "wild", EnemyKind::Trainer => "trainer", EnemyKind::Leader => "leader" }
+    }
+    pub fn from_id(id: &str) -> Option<Self> {
+        [EnemyKind::Wild, EnemyKind::Trainer, EnemyKind::Leader].into_iter().find(|k| k.id() == id)
+    }
+}
+
+/// Foto de un enemigo para el quicksave.
+pub struct SavedEnemy {
+    pub kind: EnemyKind,
+    pub x: f64,
+    pub y: f64,
+    pub chasing: bool,
+    pub creature: Option<Creature>,
+}
+
+/// Qué pasó este frame entre enemigos y jugador.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Contact { None, Hit, Battle(usize) }
+
+pub struct Enemy {
+    pub kind: EnemyKind,
+    pub sprite: AnimatedSprite,
+    pub state: EnemyState,
+    pub creature: Option<Creature>, // solo salvajes: se pueden debilitar y capturar
+    path: Vec<(usize, usize)>,
+    repath: f64,     // cuenta regresiva para recalcular A*
+    unseen: f64,     // tiempo sin ver al jugador (en persecución)
+    attack_cd: f64,
+}
+
+impl Enemy {
+    pub fn new(kind: EnemyKind, x: f64, y: f64, sheet: Rc<SpriteSheet>, creature: Option<Creature>) -> Self {
+        let mut sprite = AnimatedSprite::new(x, y, sheet);
+        sprite.scale = match kind { EnemyKind::Wild => 0.7, EnemyKind::Trainer => 0.95, EnemyKind::Leader => 1.05 };
+        if kind == EnemyKind::Leader { sprite.tint = rgb(255, 215, 140); } // ropa "dorada"
+        Self { kind, sprite, state: EnemyState::Patrol, creature, path: Vec::new(), repath: 0.0, unseen: 0.0, attack_cd: 0.0 }
+    }
+
+    fn cell(&self) -> (usize, usize) { (self.sprite.x as usize, self.sprite.y as usize) }
+
+    // Avanza hacia el siguiente punto del camino; devuelve si se movió.
+    fn follow_path(&mut self, speed: f64, dt: f64, map: &WorldMap) -> bool {
+        let Some(&(tx, ty)) = self.path.first() else { return false; };
+        let (gx, gy) = (tx as f64 + 0.5, ty as f64 + 0.5);
+        let (dx, dy) = (gx - self.sprite.x, gy - self.sprite.y);
+        let d = (dx * dx + dy * dy).sqrt();
+        let step = speed * dt;
+        if d <= step {
+            self.sprite.x = gx; self.sprite.y = gy;
+            self.path.remove(0);
+        } else {
+            let nx = self.sprite.x + dx / d * step;
+            let ny = self.sprite.y + dy / d * step;
+            if is_passable(map[ny as usize][nx as usize]) { self.sprite.x = nx; self.sprite.y = ny; }
+            else { self.path.clear(); } // el mapa cambió (hot-reload): recalcular
+        }
+        self.sprite.facing = dy.atan2(dx);
+        true
+    }
+}
+
+pub struct EnemyManager {
+    pub list: Vec<Enemy>,
+    rng: u64,
+}
+
+impl EnemyManager {
+    pub fn spawn(spots: &[(EnemyKind, f64, f64)], seed: u64, element: PokeType) -> Self {
+        let (wild, trainer) = load_sheets();
+        let mut rng = seed.rotate_left(17) | 1;
+        let list = spots.iter().map(|&(kind, x, y)| {
+            if kind == EnemyKind::Wild {
+                Enemy::new(kind, x, y, wild.clone(), Some(wild_creature(element, &mut rng)))
+            } else {
+                Enemy::new(kind, x, y, trainer.clone(), None)
+            }
+        }).collect();
+        Self { list, rng }
+    }
+
+    pub fn snapshot(&self) -> Vec<SavedEnemy> {
+        self.list.iter().map(|e| SavedEnemy {
+            kind: e.kind, x: e.sprite.x, y: e.sprite.y,
+            chasing: e.state == EnemyState::Chase, creature: e.creature.clone(),
+        }).collect()
+    }
+
+    /// Rearma los enemigos de un quicksave (caminos y timers arrancan de cero).
+    pub fn restore(saved: Vec<SavedEnemy>, seed: u64) -> Self {
+        let (wild, trainer) = load_sheets();
+        let list = saved.into_iter().map(|s| {
+            let sheet = if s.kind == EnemyKind::Wild { wild.clone() } else { trainer.clone() };
+            let mut e = Enemy::new(s.kind, s.x, s.y, sheet, s.creature);
+            if s.chasing { e.state = EnemyState::Chase; }
+            e
+        }).collect();
+        Self { list, rng: seed.rotate_left(17) | 1 }
+    }
+
+    /// IA de todos los enemigos: salvajes golpean por contacto, entrenadores piden combate.
+    pub fn update(&mut self, dt: f64, map: &WorldMap, p: &mut Player) -> Contact {
+        let mut contact = Contact::None;
+        let me = ((p.x as usize), (p.y as usize));
+        for (i, e) in self.list.iter_mut().enumerate() {
+            e.repath -= dt;
+            e.attack_cd -= dt;
+
+            let (dx, dy) = (p.x - e.sprite.x, p.y - e.sprite.y);
+            let dist = (dx * dx + dy * dy).sqrt();
+            let sees = dist <= SIGHT_RANGE && line_of_sight(map, e.sprite.x, e.sprite.y, p.x, p.y);
+
+            // Líder: quieto mirándote; al tocarlo, combate
+            if e.kind == EnemyKind::Leader {
+                if sees { e.sprite.facing = dy.atan2(dx); }
+                if dist < CONTACT_RANGE + 0.2 && e.attack_cd <= 0.0 {
+                    e.attack_cd = 0.8;
+                    contact = Contact::Battle(i);
+                }
+                e.sprite.update(dt);
+                continue;
+            }
+
+            match e.state {
+                EnemyState::Patrol => {
+                    if sees { e.state = EnemyState::Chase; e.unseen = 0.0; e.repath = 0.0; }
+                    else if e.path.is_empty() && e.repath <= 0.0 {
+                        // Nuevo destino al azar dentro de lo alcanzable (cerquita)
+                        let from = e.cell();
+                        let near: Vec<(usize, usize)> = reachable(map, from.0, from.1).into_iter()
+                            .filter(|&(x, y)| x.abs_diff(from.0) + y.abs_diff(from.1) <= 6)
+                            .collect();
+                        if !near.is_empty() {
+                            let goal = near[rand_range(&mut self.rng, near.len() as i32) as usize];
+                            e.path = find_path(map, from, goal).unwrap_or_default();
+                        }
+                        e.repath = 1.0 + rand_range(&mut self.rng, 20) as f64 * 0.1; // pausa
+                    }
+                }
+                EnemyState::Chase => {
+                    e.unseen = if sees { 0.0 } else { e.unseen + dt };
+                    if e.unseen > LOSE_SIGHT_AFTER { e.state = EnemyState::Patrol; e.path.clear(); }
+                    else if e.repath <= 0.0 {
+                        e.path = find_path(map, e.cell(), me).unwrap_or_default();
+                        e.repath = 0.4;
+                    }
+                }
+            }
+
+            let speed = if e.state == EnemyState::Chase { CHASE_SPEED } else { PATROL_SPEED };
+            // Ya en la misma zona: ir directo, sin A* (si el paso no cae en una pared;
+            // si cae, por ejemplo al doblar una esquina, sigue el camino)
+            let step = (speed * dt).min(dist);
+            let (nx, ny) = (e.sprite.x + dx / dist.max(1e-6) * step, e.sprite.y + dy / dist.max(1e-6) * step);
+            let direct = e.state == EnemyState::Chase && sees && dist < 1.0
+                && is_passable(map[ny as usize][nx as usize])
+                && line_of_sight(map, e.sprite.x, e.sprite.y, nx, ny);
+            let moved = if direct {
+                e.sprite.facing = dy.atan2(dx);
+                e.sprite.x = nx;
+                e.sprite.y = ny;
+                true
+            } else {
+                e.follow_path(speed, dt, map)
+            };
+
+            // Contacto
+            if dist < CONTACT_RANGE && e.attack_cd <= 0.0 {
+                e.attack_cd = 0.8;
+                e.sprite.play("attack");
+                if e.kind != EnemyKind::Wild {
+                    contact = Contact::Battle(i);
+                } else if contact == Contact::None {
+                    p.damage(CONTACT_DAMAGE);
+                    contact = Contact::Hit;
+                }
+            } else if e.attack_cd <= 0.4 {
+                e.sprite.play(if moved { "walk" } else { "idle" });
+            }
+            e.sprite.update(dt);
+        }
+        contact
+    }
+
+    /// Saca un enemigo del mundo (entrenador derrotado).
+    pub fn remove(&mut self, i: usize) {
+        if i < self.list.len() { self.list.remove(i); }
+    }
+
+    pub fn sprites(&self) -> impl Iterator<Item = &AnimatedSprite> {
+        self.list.iter().map(|e| &e.sprite)
+    }
+
+    /// Hot-reload de las hojas (salvaje/entrenador) que usan `path`.
+    pub fn reload(&mut self, path: &std::path::Path) {
+        for kind in [EnemyKind::Wild, EnemyKind::Trainer, EnemyKind::Leader] {
+            let Some(first) = self.list.iter().find(|e| e.kind == kind) else { continue; };
+            if !first.sprite.sheet.uses(path) { continue; }
+            let sheet = Rc::new(first.sprite.sheet.reloaded());
+            for e in self.list.iter_mut().filter(|e| e.kind == kind) { e.sprite.set_sheet(sheet.clone()); }
+        }
+    }
+}
+
+fn load_sheets() -> (Rc<SpriteSheet>, Rc<SpriteSheet>) {
+    (Rc::new(SpriteSheet::load("enemy", Fallback::Anims(gen_creature_anims))),
+     Rc::new(SpriteSheet::load("trainer", Fallback::Anims(gen_trainer_anims))))
+}
+
+// ===================== Sprite procedural (8 vistas) =====================
+
+const CREATURE_SIZE: usize = 48;
+
+// Criatura de fuego: cuerpo redondo, panza clara, ojos que giran con la vista.
+// `view` 0 = de frente; `squash` aplasta el cuerpo (paso); `mouth` abre la boca.
+fn creature_frame(view: usize, squash: f64, mouth: bool, flash: bool) -> SpriteFrame {
+    let n = CREATURE_SIZE as f64;
+    let mut rgba = vec![0u32; CREATURE_SIZE * CREATURE_SIZE];
+    let ang = view as f64 * 2.0 * std::f64::consts::PI / VIEW_ANGLES as f64;
+    let (face_x, face_vis) = (-ang.sin(), ang.cos()); // dónde cae la cara y si se ve
+    let (rx, ry) = (n * 0.40 * (1.0 + squash * 0.5), n * 0.40 * (1.0 - squash));
+    let (cx, cy) = (n / 2.0, n - ry - 1.0);
+
+    for y in 0..CREATURE_SIZE {
+        for x in 0..CREATURE_SIZE {
+            let (fx, fy) = ((x as f64 - cx) / rx, (y as f64 - cy) / ry);
+            let mut col = None;
+            if fx * fx + fy * fy <= 1.0 {
+                let mut c = if flash { rgb(255, 120, 90) } else { rgb(230, 90, 40) };
+                // Panza (se corre con la vista)
+                let bx = fx - face_x * 0.35;
+                if face_vis > -0.2 && bx * bx + (fy - 0.35).powi(2) <= 0.28 { c = rgb(250, 200, 120); }
+                if face_vis > -0.3 {
+                    // Ojos
+                    for side in [-1.0, 1.0] {
+                        let ex = face_x * 0.45 + side * 0.28 * face_vis.max(0.2);
+                        if (fx - ex).powi(2) + (fy + 0.25).powi(2) <= 0.018 { c = rgb(20, 20, 30); }
+                    }
+                    if mouth && (fx - face_x * 0.45).powi(2) * 2.0 + (fy - 0.15).powi(2) <= 0.02 {
+                        c = rgb(120, 10, 10);
+                    }
+                }
+                col = Some(c);
+            }
+            // Llamita en la cabeza
+            let (lx, ly) = (x as f64 - cx, y as f64 - (cy - ry));
+            if ly < 0.0 && ly > -n * 0.22 && lx.abs() < (n * 0.09) * (1.0 + ly / (n * 0.22)) {
+                col = Some(rgb(255, 210, 60));
+            }
+            if let Some(c) = col { rgba[y * CREATURE_SIZE + x] = 0xFF00_0000 | c; }
+        }
+    }
+    SpriteFrame { w: CREATURE_SIZE, h: CREATURE_SIZE, rgba }
+}
+
+fn creature_anim(name: &str, dur: f64, looping: bool, frames: &[(f64, bool, bool)]) -> Animation {
+    let views = (0..VIEW_ANGLES)
+        .map(|v| frames.iter().map(|&(sq, mouth, flash)| creature_frame(v, sq, mouth, flash)).collect())
+        .collect();
+    Animation { name: name.to_string(), views, durations: vec![dur; frames.len()], looping }
+}
+
+fn gen_creature_anims() -> Vec<Animation> {
+    vec![
+        creature_anim("idle", 0.5, true, &[(0.0, false, false), (0.05, false, false)]),
+        creature_anim("walk", 0.15, true, &[(0.0, false, false), (0.15, false, false)]),
+        creature_anim("attack", 0.12, false, &[(0.1, true, true), (0.0, true, false)]),
+    ]
+}
+
+// Entrenador: figura con gorra roja y chaqueta; de espaldas se ve la mochila.
+fn trainer_frame(view: usize, step: i32, wave: bool) -> SpriteFrame {
+    let (w, h) = (32usize, 64usize);
+    let mut rgba = vec![0u32; w * h];
+    let ang = view as f64 * 2.0 * std::f64::consts::PI / VIEW_ANGLES as f64;
+    let (face_x, face_vis) = (-ang.sin(), ang.cos());
+    let put = |rgba: &mut Vec<u32>, x: i32, y: i32, c: u32| {
+        if x >= 0 && y >= 0 && (x as usize) < w && (y as usize) < h { rgba[y as usize * w + x as usize] = 0xFF00_0000 | c; }
+    };
+    let cx = 16i32;
+    // Piernas (alternan con el paso)
+    for y in 44..64 {
+        put(&mut rgba, cx - 4 + if y > 54 { step } else { 0 }, y, rgb(40, 50, 90));
+        put(&mut rgba, cx - 5 + if y > 54 { step } else { 0 }, y, rgb(40, 50, 90));
+        put(&mut rgba, cx + 4 - if y > 54 { step } else { 0 }, y, rgb(40, 50, 90));
+        put(&mut rgba, cx + 3 - if y > 54 { step } else { 0 }, y, rgb(40, 50, 90));
+    }
+    // Torso + brazos
+    for y in 22..45 {
+        for x in (cx - 8)..=(cx + 8) {
+            let mut c = rgb(40, 110, 200);
+            if face_vis < -0.3 && (x - cx).abs() <= 5 && (26..40).contains(&y) { c = rgb(200, 170, 60); } // mochila
+            put(&mut rgba, x, y, c);
+        }
+    }
+    let arm_up = if wave { 12 } else { 0 };
+    for y in (24 - arm_up)..(42 - arm_up) { put(&mut rgba, cx + 10, y, rgb(230, 190, 150)); put(&mut rgba, cx + 11, y, rgb(230, 190, 150)); }
+    for y in 24..42 { put(&mut rgba, cx - 10, y, rgb(230, 190, 150)); put(&mut rgba, cx - 11, y, rgb(230, 190, 150)); }
+    // Cabeza
+    for y in 8..22i32 {
+        for x in (cx - 6)..=(cx + 6) {
+            if (x - cx).pow(2) + (y - 15).pow(2) <= 40 { put(&mut rgba, x, y, if face_vis < -0.3 { rgb(70, 40, 20) } else { rgb(240, 200, 160) }); }
+        }
+    }
+    if face_vis > -0.3 {
+        let ex = cx + (face_x * 3.0) as i32;
+        put(&mut rgba, ex - 2, 15, rgb(20, 20, 30));
+        put(&mut rgba, ex + 2, 15, rgb(20, 20, 30));
+    }
+    // Gorra
+    for y in 5..11 {
+        for x in (cx - 7)..=(cx + 7) { if y > 7 || (x - cx).abs() <= 5 { put(&mut rgba, x, y, rgb(220, 40, 40)); } }
+    }
+    let visor = cx + (face_x * 7.0) as i32;
+    if face_vis > -0.5 { for x in (visor - 4)..=(visor + 4) { put(&mut rgba, x, 10, rgb(180, 20, 20)); } }
+    SpriteFrame { w, h, rgba }
+}
+
+fn trainer_anim(name: &str, dur: f64, looping: bool, frames: &[(i32, bool)]) -> Animation {
+    let views = (0..VIEW_ANGLES)
+        .map(|v| frames.iter().map(|&(step, wave)| trainer_frame(v, step, wave)).collect())
+        .collect();
+    Animation { name: name.to_string(), views, durations: vec![dur; frames.len()], looping }
+}
+
+pub fn gen_trainer_anims() -> Vec<Animation> {
+    vec![
+        trainer_anim("idle", 0.6, true, &[(0, false)]),
+        trainer_anim("walk", 0.18, true, &[(2, false), (-2, false)]),
+        trainer_anim("attack", 0.2, false, &[(0, true), (0, false)]),
+    ]
+}
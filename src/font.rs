@@ -0,0 +1,339 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+
+// Fuentes externas opcionales (la primera que cargue gana; si no, la de 5x7 de acá)
+pub const BDF_PATH: &str = "assets/font.bdf";
+pub const SHEET_PATH: &str = "assets/font.png";
+pub const SHEET_META: &str = "assets/font.txt";
+
+/// Un glifo: filas de bits (el bit más alto es la columna de la izquierda).
+/// `top` es la fila donde empieza respecto de la parte de arriba del texto
+/// (negativo = acentos sobre las mayúsculas, y pasa de la altura con las
+/// descendentes); `advance` cuánto se corre el cursor, espacio incluido.
+pub struct Glyph {
+    pub w: usize,
+    pub left: i32,
+    pub top: i32,
+    pub rows: Vec<u32>,
+    pub advance: usize,
+}
+
+impl Glyph {
+    pub fn on(&self, x: usize, y: usize) -> bool {
+        (self.rows[y] >> (self.w - 1 - x)) & 1 == 1
+    }
+}
+
+pub struct Font {
+    glyphs: HashMap<char, Glyph>,
+    missing: Glyph, // bloque para lo que no esté
+}
+
+impl Font {
+    /// La de siempre: 5x7 monoespaciada (6 de avance), con minúsculas,
+    /// acentos, signos de apertura y flechas.
+    pub fn builtin() -> Self {
+        let glyphs = BUILTIN.iter().map(|&(ch, top, rows)| {
+            (ch, Glyph { w: 5, left: 0, top, rows: rows.iter().map(|&r| r as u32).collect(), advance: 6 })
+        });
+        let mut glyphs: HashMap<char, Glyph> = glyphs.collect();
+        glyphs.insert(' ', Glyph { w: 5, left: 0, top: 0, rows: Vec::new(), advance: 6 });
+        Self { glyphs, missing: Glyph { w: 5, left: 0, top: 0, rows: vec![0x1F; 6], advance: 6 } }
+    }
+
+    /// `assets/font.bdf`, si no `assets/font.png` + `assets/font.txt`, si no la integrada.
+    pub fn load() -> Self {
+        if Path::new(BDF_PATH).exists() {
+            match load_bdf(BDF_PATH) {
+                Some(f) => return f,
+                None => eprintln!("{BDF_PATH}: no se pudo leer la fuente BDF"),
+            }
+        }
+        if Path::new(SHEET_PATH).exists() {
+            match load_sheet(SHEET_PATH, SHEET_META) {
+                Some(f) => return f,
+                None => eprintln!("{SHEET_PATH}: falta {SHEET_META} o no coincide con la imagen"),
+            }
+        }
+        Self::builtin()
+    }
+
+    /// ¿El archivo cambiado es de la fuente? (hot-reload)
+    pub fn uses(path: &Path) -> bool {
+        [BDF_PATH, SHEET_PATH, SHEET_META].iter().any(|p| Path::new(p) == path)
+    }
+
+    /// El glifo de `ch`; si la fuente no lo trae prueba sin acento y en
+    /// mayúscula (las fuentes externas suelen venir cortas).
+    pub fn glyph(&self, ch: char) -> &Glyph {
+        let plain = without_accent(ch);
+        [ch, plain, plain.to_ascii_uppercase()].iter()
+            .find_map(|c| self.glyphs.get(c))
+            .unwrap_or(&self.missing)
+    }
+}
+
+fn without_accent(ch: char) -> char {
+    match ch {
+        'á' | 'à' | 'ä' | 'â' => 'a', 'é' | 'è' | 'ë' | 'ê' => 'e', 'í' | 'ì' | 'ï' | 'î' => 'i',
+        'ó' | 'ò' | 'ö' | 'ô' => 'o', 'ú' | 'ù' | 'ü' | 'û' => 'u', 'ñ' => 'n',
+        'Á' | 'À' | 'Ä' | 'Â' => 'A', 'É' | 'È' | 'Ë' | 'Ê' => 'E', 'Í' | 'Ì' | 'Ï' | 'Î' => 'I',
+        'Ó' | 'Ò' | 'Ö' | 'Ô' => 'O', 'Ú' | 'Ù' | 'Ü' | 'Û' => 'U', 'Ñ' => 'N',
+        '¡' => '!', '¿' => '?', '•' | '·' => '.',
+        c => c,
+    }
+}
+
+// BDF (formato de texto de X11): FONT_ASCENT marca dónde empieza el texto y
+// cada glifo trae ENCODING (unicode), DWIDTH (avance), BBX y las filas en hex.
+fn load_bdf(path: &str) -> Option<Font> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let (mut ascent, mut bounds) = (None, None);
+    let mut glyphs = HashMap::new();
+    let (mut code, mut advance, mut bbx) = (None, 0usize, (0usize, 0usize, 0i32, 0i32));
+    let mut bitmap: Option<Vec<u32>> = None;
+    let mut bad = false; // glifo con algún número roto: se saltea (el resto de la fuente sirve)
+    for line in text.lines() {
+        let w: Vec<&str> = line.split_whitespace().collect();
+        let num = |i: usize| w.get(i).and_then(|s| s.parse::<i32>().ok());
+        match w.first().copied() {
+            Some("FONT_ASCENT") => ascent = num(1),
+            Some("FONTBOUNDINGBOX") => if let (Some(bw), Some(bh), Some(x), Some(y)) = (num(1), num(2), num(3), num(4)) {
+                bounds = Some((bw, bh, x, y));
+            },
+            Some("STARTCHAR") => { code = None; advance = 0; bbx = (0, 0, 0, 0); bad = false; }
+            Some("ENCODING") => code = num(1).and_then(|c| char::from_u32(c as u32)),
+            Some("DWIDTH") => match num(1) {
+                Some(a) => advance = a.max(0) as usize,
+                None => bad = true,
+            },
+            Some("BBX") => match (num(1), num(2), num(3), num(4)) {
+                (Some(bw), Some(bh), Some(x), Some(y)) => bbx = (bw.clamp(0, 32) as usize, bh.max(0) as usize, x, y),
+                _ => bad = true,
+            },
+            Some("BITMAP") => bitmap = Some(Vec::new()),
+            Some("ENDCHAR") => {
+                let rows = bitmap.take().unwrap_or_default();
+                let top_of = ascent.or(bounds.map(|(_, h, _, y)| h + y));
+                let (bw, bh, xoff, yoff) = bbx;
+                match (code, top_of) {
+                    (Some(ch), Some(asc)) if !bad && bw > 0 && rows.len() == bh => {
+                        glyphs.insert(ch, Glyph { w: bw, left: xoff, top: asc - (yoff + bh as i32), rows, advance });
+                    }
+                    (Some(ch), _) if !bad && bw == 0 => {
+                        glyphs.insert(ch, Glyph { w: 1, left: 0, top: 0, rows: Vec::new(), advance });
+                    }
+                    _ => {}
+                }
+            }
+            // Fila en hex: solo interesan las primeras 32 columnas (los glifos más anchos se recortan)
+            Some(hex) if bitmap.is_some() => {
+                let digits = hex.get(..hex.len().min(8));
+                match digits.and_then(|d| u32::from_str_radix(d, 16).ok()) {
+                    Some(v) => {
+                        let total = digits.map_or(0, str::len) * 4;
+                        let row = if total >= bbx.0 { v >> (total - bbx.0) } else { v << (bbx.0 - total) };
+                        if let Some(b) = bitmap.as_mut() { b.push(row); }
+                    }
+                    None => bad = true,
+                }
+            }
+            _ => {}
+        }
+    }
+    if glyphs.is_empty() { return None; }
+    // Lo que falte se dibuja como un bloque del tamaño de la fuente
+    let asc = ascent.or(bounds.map(|(_, h, _, y)| h + y))?;
+    let (fw, fh, fx, fy) = bounds.unwrap_or((asc / 2, asc, 0, 0));
+    let w = fw.clamp(1, 32) as usize;
+    let missing = Glyph { w, left: fx, top: asc - (fy + fh), rows: vec![u32::MAX >> (32 - w); fh.max(1) as usize], advance: w + 1 };
+    Some(Font { glyphs, missing })
+}
+
+// Hoja PNG: una grilla de celdas iguales. El .txt dice el tamaño de celda y,
+// fila por fila, qué caracteres hay:
+//   cell 8 12
+//   row ABCDEFGHIJKLMNOP
+// Los glifos son los píxeles claros y opacos; el ancho de cada uno sale de
+// sus columnas usadas (así la fuente queda proporcional).
+fn load_sheet(path: &str, meta: &str) -> Option<Font> {
+    let img = image::open(path).ok()?.to_rgba8();
+    let text = std::fs::read_to_string(meta).ok()?;
+    let mut cell = None;
+    let mut rows: Vec<Vec<char>> = Vec::new();
+    for line in text.lines() {
+        if let Some(r) = line.strip_prefix("row ") { rows.push(r.chars().collect()); }
+        else if let Some(c) = line.strip_prefix("cell ") {
+            let mut it = c.split_whitespace().filter_map(|n| n.parse::<u32>().ok());
+            cell = Some((it.next()?, it.next()?));
+        }
+    }
+    let (cw, ch) = cell?;
+    if !(2..=32).contains(&cw) || ch == 0 || rows.len() as u32 * ch > img.height() { return None; }
+    let lit = |x: u32, y: u32| {
+        let p = img.get_pixel(x, y);
+        p[3] >= 128 && (p[0] as u32 + p[1] as u32 + p[2] as u32) / 3 > 96
+    };
+
+    let mut glyphs = HashMap::new();
+    for (gy, chars) in rows.iter().enumerate() {
+        for (gx, &c) in chars.iter().enumerate() {
+            let (x0, y0) = (gx as u32 * cw, gy as u32 * ch);
+            if x0 + cw > img.width() { break; }
+            let used: Vec<u32> = (0..cw).filter(|&x| (0..ch).any(|y| lit(x0 + x, y0 + y))).collect();
+            let g = match (used.first(), used.last()) {
+                (Some(&a), Some(&b)) => {
+                    let w = (b - a + 1) as usize;
+                    let rows = (0..ch).map(|y| {
+                        (a..=b).fold(0u32, |acc, x| (acc << 1) | lit(x0 + x, y0 + y) as u32)
+                    }).collect();
+                    Glyph { w, left: 0, top: 0, rows, advance: w + 1 }
+                }
+                _ => Glyph { w: 1, left: 0, top: 0, rows: Vec::new(), advance: (cw as usize / 2).max(1) },
+            };
+            glyphs.insert(c, g);
+        }
+    }
+    let missing = Glyph { w: cw as usize - 1, left: 0, top: 0, rows: vec![u32::MAX >> (33 - cw); ch as usize - 1], advance: cw as usize };
+    Some(Font { glyphs, missing })
+}
+
+// La fuente en uso (el juego dibuja desde un solo hilo). `set` la cambia
+// al arrancar o con el hot-reload.
+thread_local! {
+    static FONT: RefCell<Font> = RefCell::new(Font::builtin());
+}
+
+pub fn set(font: Font) {
+    FONT.with(|f| *f.borrow_mut() = font);
+}
+
+pub fn with<R>(f: impl FnOnce(&Font) -> R) -> R {
+    FONT.with(|font| f(&font.borrow()))
+}
+
+// Filas de 5 bits (bit 4 = columna izquierda) y desde qué fila empiezan.
+// Mayúsculas y números ocupan las filas 0..7; las minúsculas arrancan en la
+// 2 (las que tienen rabito bajan hasta la 8) y los acentos de mayúscula en la -2.
+const BUILTIN: &[(char, i32, &[u8])] = &[
+    ('A', 0, &[0x0E,0x11,0x11,0x1F,0x11,0x11,0x11]),
+    ('B', 0, &[0x1E,0x11,0x11,0x1E,0x11,0x11,0x1E]),
+    ('C', 0, &[0x0E,0x11,0x10,0x10,0x10,0x11,0x0E]),
+    ('D', 0, &[0x1E,0x11,0x11,0x11,0x11,0x11,0x1E]),
+    ('E', 0, &[0x1F,0x10,0x10,0x1E,0x10,0x10,0x1F]),
+    ('F', 0, &[0x1F,0x10,0x10,0x1E,0x10,0x10,0x10]),
+    ('G', 0, &[0x0E,0x11,0x10,0x17,0x11,0x11,0x0F]),
+    ('H', 0, &[0x11,0x11,0x11,0x1F,0x11,0x11,0x11]),
+    ('I', 0, &[0x0E,0x04,0x04,0x04,0x04,0x04,0x0E]),
+    ('J', 0, &[0x07,0x02,0x02,0x02,0x02,0x12,0x0C]),
+    ('K', 0, &[0x11,0x12,0x14,0x18,0x14,0x12,0x11]),
+    ('L', 0, &[0x10,0x10,0x10,0x10,0x10,0x10,0x1F]),
+    ('M', 0, &[0x11,0x1B,0x15,0x15,0x11,0x11,0x11]),
+    ('N', 0, &[0x11,0x11,0x19,0x15,0x13,0x11,0x11]),
+    ('O', 0, &[0x0E,0x11,0x11,0x11,0x11,0x11,0x0E]),
+    ('P', 0, &[0x1E,0x11,0x11,0x1E,0x10,0x10,0x10]),
+    ('Q', 0, &[0x0E,0x11,0x11,0x11,0x15,0x12,0x0D]),
+    ('R', 0, &[0x1E,0x11,0x11,0x1E,0x14,0x12,0x11]),
+    ('S', 0, &[0x0F,0x10,0x10,0x0E,0x01,0x01,0x1E]),
+    ('T', 0, &[0x1F,0x04,0x04,0x04,0x04,0x04,0x04]),
+    ('U', 0, &[0x11,0x11,0x11,0x11,0x11,0x11,0x0E]),
+    ('V', 0, &[0x11,0x11,0x11,0x11,0x11,0x0A,0x04]),
+    ('W', 0, &[0x11,0x11,0x11,0x15,0x15,0x15,0x0A]),
+    ('X', 0, &[0x11,0x11,0x0A,0x04,0x0A,0x11,0x11]),
+    ('Y', 0, &[0x11,0x11,0x11,0x0A,0x04,0x04,0x04]),
+    ('Z', 0, &[0x1F,0x01,0x02,0x04,0x08,0x10,0x1F]),
+    ('0', 0, &[0x0E,0x11,0x13,0x15,0x19,0x11,0x0E]),
+    ('1', 0, &[0x04,0x0C,0x04,0x04,0x04,0x04,0x0E]),
+    ('2', 0, &[0x0E,0x11,0x01,0x02,0x04,0x08,0x1F]),
+    ('3', 0, &[0x1F,0x02,0x04,0x02,0x01,0x11,0x0E]),
+    ('4', 0, &[0x02,0x06,0x0A,0x12,0x1F,0x02,0x02]),
+    ('5', 0, &[0x1F,0x10,0x1E,0x01,0x01,0x11,0x0E]),
+    ('6', 0, &[0x06,0x08,0x10,0x1E,0x11,0x11,0x0E]),
+    ('7', 0, &[0x1F,0x01,0x02,0x04,0x08,0x08,0x08]),
+    ('8', 0, &[0x0E,0x11,0x11,0x0E,0x11,0x11,0x0E]),
+    ('9', 0, &[0x0E,0x11,0x11,0x0F,0x01,0x02,0x0C]),
+    ('!', 0, &[0x04,0x04,0x04,0x04,0x04,0x00,0x04]),
+    ('"', 0, &[0x0A,0x0A,0x0A]),
+    ('#', 0, &[0x0A,0x0A,0x1F,0x0A,0x1F,0x0A,0x0A]),
+    ('$', 0, &[0x04,0x0F,0x14,0x0E,0x05,0x1E,0x04]),
+    ('%', 0, &[0x18,0x19,0x02,0x04,0x08,0x13,0x03]),
+    ('&', 0, &[0x0C,0x12,0x14,0x08,0x15,0x12,0x0D]),
+    ('\'', 0, &[0x04,0x04,0x08]),
+    ('(', 0, &[0x02,0x04,0x08,0x08,0x08,0x04,0x02]),
+    (')', 0, &[0x08,0x04,0x02,0x02,0x02,0x04,0x08]),
+    ('*', 0, &[0x00,0x04,0x15,0x0E,0x15,0x04,0x00]),
+    ('+', 0, &[0x00,0x04,0x04,0x1F,0x04,0x04,0x00]),
+    (',', 5, &[0x06,0x02,0x04]),
+    ('-', 0, &[0x00,0x00,0x00,0x1F,0x00,0x00,0x00]),
+    ('.', 5, &[0x0C,0x0C]),
+    ('/', 0, &[0x00,0x01,0x02,0x04,0x08,0x10,0x00]),
+    (':', 1, &[0x0C,0x0C,0x00,0x0C,0x0C]),
+    (';', 1, &[0x0C,0x0C,0x00,0x0C,0x04,0x08]),
+    ('<', 0, &[0x02,0x04,0x08,0x10,0x08,0x04,0x02]),
+    ('=', 2, &[0x1F,0x00,0x1F]),
+    ('>', 0, &[0x08,0x04,0x02,0x01,0x02,0x04,0x08]),
+    ('?', 0, &[0x0E,0x11,0x01,0x02,0x04,0x00,0x04]),
+    ('@', 0, &[0x0E,0x11,0x01,0x0D,0x15,0x15,0x0E]),
+    ('[', 0, &[0x0E,0x08,0x08,0x08,0x08,0x08,0x0E]),
+    ('\\', 0, &[0x00,0x10,0x08,0x04,0x02,0x01,0x00]),
+    (']', 0, &[0x0E,0x02,0x02,0x02,0x02,0x02,0x0E]),
+    ('^', 0, &[0x04,0x0A,0x11]),
+    ('_', 6, &[0x1F]),
+    ('`', 0, &[0x08,0x04,0x02]),
+    ('{', 0, &[0x02,0x04,0x04,0x08,0x04,0x04,0x02]),
+    ('|', 0, &[0x04,0x04,0x04,0x04,0x04,0x04,0x04]),
+    ('}', 0, &[0x08,0x04,0x04,0x02,0x04,0x04,0x08]),
+    ('~', 2, &[0x08,0x15,0x02]),
+    ('a', 2, &[0x0E,0x01,0x0F,0x11,0x0F]),
+    ('b', 0, &[0x10,0x10,0x16,0x19,0x11,0x11,0x1E]),
+    ('c', 2, &[0x0E,0x10,0x10,0x11,0x0E]),
+    ('d', 0, &[0x01,0x01,0x0D,0x13,0x11,0x11,0x0F]),
+    ('e', 2, &[0x0E,0x11,0x1F,0x10,0x0E]),
+    ('f', 0, &[0x06,0x09,0x08,0x1C,0x08,0x08,0x08]),
+    ('g', 2, &[0x0F,0x11,0x11,0x0F,0x01,0x11,0x0E]),
+    ('h', 0, &[0x10,0x10,0x16,0x19,0x11,0x11,0x11]),
+    ('i', 0, &[0x04,0x00,0x0C,0x04,0x04,0x04,0x0E]),
+    ('j', 0, &[0x02,0x00,0x06,0x02,0x02,0x02,0x02,0x12,0x0C]),
+    ('k', 0, &[0x10,0x10,0x12,0x14,0x18,0x14,0x12]),
+    ('l', 0, &[0x0C,0x04,0x04,0x04,0x04,0x04,0x0E]),
+    ('m', 2, &[0x1A,0x15,0x15,0x11,0x11]),
+    ('n', 2, &[0x16,0x19,0x11,0x11,0x11]),
+    ('o', 2, &[0x0E,0x11,0x11,0x11,0x0E]),
+    ('p', 2, &[0x1E,0x11,0x11,0x1E,0x10,0x10,0x10]),
+    ('q', 2, &[0x0F,0x11,0x11,0x0F,0x01,0x01,0x01]),
+    ('r', 2, &[0x16,0x19,0x10,0x10,0x10]),
+    ('s', 2, &[0x0F,0x10,0x0E,0x01,0x1E]),
+    ('t', 0, &[0x08,0x08,0x1C,0x08,0x08,0x09,0x06]),
+    ('u', 2, &[0x11,0x11,0x11,0x13,0x0D]),
+    ('v', 2, &[0x11,0x11,0x11,0x0A,0x04]),
+    ('w', 2, &[0x11,0x11,0x15,0x15,0x0A]),
+    ('x', 2, &[0x11,0x0A,0x04,0x0A,0x11]),
+    ('y', 2, &[0x11,0x11,0x11,0x0F,0x01,0x11,0x0E]),
+    ('z', 2, &[0x1F,0x02,0x04,0x08,0x1F]),
+    ('Á', -2, &[0x02,0x04,0x0E,0x11,0x11,0x1F,0x11,0x11,0x11]),
+    ('É', -2, &[0x02,0x04,0x1F,0x10,0x10,0x1E,0x10,0x10,0x1F]),
+    ('Í', -2, &[0x02,0x04,0x0E,0x04,0x04,0x04,0x04,0x04,0x0E]),
+    ('Ó', -2, &[0x02,0x04,0x0E,0x11,0x11,0x11,0x11,0x11,0x0E]),
+    ('Ú', -2, &[0x02,0x04,0x11,0x11,0x11,0x11,0x11,0x11,0x0E]),
+    ('Ü', -2, &[0x00,0x0A,0x11,0x11,0x11,0x11,0x11,0x11,0x0E]),
+    ('Ñ', -2, &[0x0A,0x14,0x11,0x11,0x19,0x15,0x13,0x11,0x11]),
+    ('á', 0, &[0x02,0x04,0x0E,0x01,0x0F,0x11,0x0F]),
+    ('é', 0, &[0x02,0x04,0x0E,0x11,0x1F,0x10,0x0E]),
+    ('ó', 0, &[0x02,0x04,0x0E,0x11,0x11,0x11,0x0E]),
+    ('ú', 0, &[0x02,0x04,0x11,0x11,0x11,0x13,0x0D]),
+    ('í', 0, &[0x02,0x04,0x0C,0x04,0x04,0x04,0x0E]),
+    ('ü', 0, &[0x00,0x0A,0x11,0x11,0x11,0x13,0x0D]),
+    ('ñ', 0, &[0x0A,0x14,0x16,0x19,0x11,0x11,0x11]),
+    ('¡', 0, &[0x04,0x00,0x04,0x04,0x04,0x04,0x04]),
+    ('¿', 0, &[0x04,0x00,0x04,0x08,0x10,0x11,0x0E]),
+    ('°', 0, &[0x0C,0x12,0x0C]),
+    ('•', 2, &[0x00,0x0E,0x0E,0x0E]),
+    ('←', 0, &[0x00,0x04,0x08,0x1F,0x08,0x04,0x00]),
+    ('→', 0, &[0x00,0x04,0x02,0x1F,0x02,0x04,0x00]),
+    ('↑', 0, &[0x04,0x0E,0x15,0x04,0x04,0x04,0x04]),
+    ('↓', 0, &[0x04,0x04,0x04,0x04,0x15,0x0E,0x04]),
+    ('…', 5, &[0x00,0x15]),
+    ('·', 3, &[0x0C,0x0C]),
+    ('×', 1, &[0x11,0x0A,0x04,0x0A,0x11]),
+];
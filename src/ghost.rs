@@ -0,0 +1,95 @@
+use std::rc::Rc;
+
+use crate::constants::{rgb, TICK};
+use crate::enemies::gen_trainer_anims;
+use crate::player::Player;
+use crate::save;
+use crate::sprites::{AnimatedSprite, Fallback, SpriteSheet};
+
+pub const GHOST_DIR: &str = "ghosts";
+const SAMPLE_EVERY: usize = 6; // ticks entre muestras del camino (20 por segundo)
+const KEEP: usize = 10;        // fantasmas por nivel (los de semillas más viejas se borran)
+
+/// Mejor corrida de un nivel con una semilla: tiempo, parciales y el camino
+/// recorrido, para correr contra ella la próxima vez.
+pub struct Ghost {
+    pub time: f64,
+    pub splits: Vec<f64>,
+    path: Vec<(f64, f64, f64)>, // x, y, hacia dónde mira
+    ticks: usize,
+}
+
+impl Ghost {
+    /// Vacío, para ir grabando la corrida en curso.
+    pub fn new() -> Self {
+        Self { time: 0.0, splits: Vec::new(), path: Vec::new(), ticks: 0 }
+    }
+
+    /// Un tick más de la corrida en curso (se guarda una muestra cada tanto).
+    pub fn record(&mut self, p: &Player) {
+        if self.ticks.is_multiple_of(SAMPLE_EVERY) {
+            self.path.push((p.x, p.y, p.dir_y.atan2(p.dir_x)));
+        }
+        self.ticks += 1;
+    }
+
+    /// Dónde iba a los `t` segundos (al terminar se queda parado en la meta).
+    pub fn at(&self, t: f64) -> Option<(f64, f64, f64)> {
+        let f = t / (TICK * SAMPLE_EVERY as f64);
+        let i = (f as usize).min(self.path.len().checked_sub(1)?);
+        let (a, b) = (self.path[i], self.path[(i + 1).min(self.path.len() - 1)]);
+        let k = (f - i as f64).clamp(0.0, 1.0);
+        Some((a.0 + (b.0 - a.0) * k, a.1 + (b.1 - a.1) * k, b.2))
+    }
+
+    fn file(level: usize, seed: u64) -> String {
+        format!("{GHOST_DIR}/level_{level}_{seed}.txt")
+    }
+
+    pub fn load(level: usize, seed: u64) -> Option<Self> {
+        let lines = save::read_versioned(&Self::file(level, seed))?;
+        let num = |w: &Vec<String>, i: usize| -> Option<f64> { w.get(i)?.parse().ok() };
+        let time = num(lines.iter().find(|l| l[0] == "time")?, 1)?;
+        let splits = lines.iter().filter(|l| l[0] == "split").filter_map(|l| num(l, 1)).collect();
+        let path: Vec<_> = lines.iter().filter(|l| l[0] == "p")
+            .filter_map(|l| Some((num(l, 1)?, num(l, 2)?, num(l, 3)?)))
+            .collect();
+        if path.is_empty() { return None; }
+        Some(Self { time, splits, ticks: path.len() * SAMPLE_EVERY, path })
+    }
+
+    /// Guarda esta corrida como la mejor de (nivel, semilla).
+    pub fn save(&self, level: usize, seed: u64) {
+        let mut out = save::header("fantasma: mejor corrida de un nivel con una semilla");
+        out.push_str(&format!("time {:.3}\n", self.time));
+        for s in &self.splits { out.push_str(&format!("split {s:.3}\n")); }
+        for (x, y, f) in &self.path { out.push_str(&format!("p {x:.3} {y:.3} {f:.3}\n")); }
+        let res = std::fs::create_dir_all(GHOST_DIR).and_then(|_| std::fs::write(Self::file(level, seed), out));
+        if let Err(e) = res { eprintln!("No se pudo guardar el fantasma: {e}"); }
+        prune(level);
+    }
+}
+
+// Cada semilla nueva deja su archivo: del nivel quedan solo los `KEEP` más recientes.
+fn prune(level: usize) {
+    let Ok(dir) = std::fs::read_dir(GHOST_DIR) else { return; };
+    let prefix = format!("level_{level}_");
+    let mut files: Vec<_> = dir.flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with(&prefix))
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .collect();
+    files.sort_by_key(|f| std::cmp::Reverse(f.0));
+    for (_, path) in files.into_iter().skip(KEEP) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Sprite del fantasma: un entrenador azulado y translúcido.
+pub fn sprite() -> AnimatedSprite {
+    let sheet = Rc::new(SpriteSheet::load("ghost", Fallback::Anims(gen_trainer_anims)));
+    let mut s = AnimatedSprite::new(0.0, 0.0, sheet);
+    s.play("walk");
+    s.tint = rgb(150, 200, 255);
+    s.alpha = 110;
+    s
+}
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Vigilante de archivos para el modo dev.
+/// Hace polling de la fecha de modificación (sin dependencias ni hilos):
+/// cada `interval` segundos revisa los directorios/archivos registrados
+/// y devuelve los que cambiaron (o aparecieron) desde la última revisión.
+pub struct AssetWatcher {
+    dirs: Vec<PathBuf>,
+    files: Vec<PathBuf>,
+    seen: Vec<(PathBuf, Option<SystemTime>)>,
+    interval: f64,
+    acc: f64,
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// Lista recursiva de archivos dentro de `dir` (si no existe, nada).
+fn scan_dir(dir: &Path, out: &mut Vec<PathBuf>) {
+    if let Ok(rd) = fs::read_dir(dir) {
+        for entry in rd.flatten() {
+            let p = entry.path();
+            if p.is_dir() { scan_dir(&p, out); } else { out.push(p); }
+        }
+    }
+}
+
+impl AssetWatcher {
+    pub fn new(interval: f64) -> Self {
+        Self { dirs: Vec::new(), files: Vec::new(), seen: Vec::new(), interval, acc: 0.0 }
+    }
+
+    pub fn watch_dir(&mut self, dir: &str) {
+        self.dirs.push(PathBuf::from(dir));
+        self.snapshot();
+    }
+
+    /// Archivo suelto (p.ej. un nivel fuera de `assets/`). Puede no existir aún.
+    pub fn watch_file(&mut self, path: &str) {
+        let p = PathBuf::from(path);
+        if !self.files.contains(&p) { self.files.push(p); }
+        self.snapshot();
+    }
+
+    fn current(&self) -> Vec<PathBuf> {
+        let mut all = Vec::new();
+        for d in &self.dirs { scan_dir(d, &mut all); }
+        for f in &self.files {
+            if !all.contains(f) { all.push(f.clone()); }
+        }
+        all
+    }
+
+    // Toma la foto inicial sin reportar cambios.
+    fn snapshot(&mut self) {
+        self.seen = self.current().into_iter().map(|p| { let t = mtime(&p); (p, t) }).collect();
+    }
+
+    /// Devuelve los archivos modificados/creados desde el último poll.
+    pub fn poll(&mut self, dt: f64) -> Vec<PathBuf> {
+        self.acc += dt;
+        if self.acc < self.interval { return Vec::new(); }
+        self.acc = 0.0;
+
+        let mut changed = Vec::new();
+        for p in self.current() {
+            let t = mtime(&p);
+            match self.seen.iter_mut().find(|(q, _)| *q == p) {
+                Some((_, old)) => {
+                    if *old != t { *old = t; changed.push(p); }
+                }
+                None => {
+                    self.seen.push((p.clone(), t));
+                    changed.push(p);
+                }
+            }
+        }
+        changed
+    }
+}
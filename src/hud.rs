@@ -1,97 +1,131 @@
-use crate::constants::{rgb, WIDTH, HEIGHT, MAP_W, MAP_H};
-use crate::world::WorldMap;
+use crate::constants::{rgb, WIDTH, HEIGHT};
+use crate::items::{Inventory, ITEM_KINDS};
+use crate::font::{self, Glyph};
+use crate::layout::Area;
 
 #[inline]
 pub fn put_pixel(buf: &mut [u32], x: usize, y: usize, c: u32) {
     if x < WIDTH && y < HEIGHT { buf[y * WIDTH + x] = c; }
 }
 
-pub fn draw_minimap(buf: &mut [u32], map: &WorldMap, px: f64, py: f64, dx: f64, dy: f64) {
-    const SCALE: usize = 4;
-    let off_x = 10usize;
-    let off_y = 10usize;
-
-    for my in 0..MAP_H {
-        for mx in 0..MAP_W {
-            let cell = map[my][mx];
-            let base = match cell {
-                0 => rgb(28,28,36),
-                9 => rgb(255,180,80),
-                _ => rgb(90,90,110),
-            };
-            for pyx in 0..SCALE {
-                for pxx in 0..SCALE {
-                    put_pixel(buf, off_x + mx*SCALE + pxx, off_y + my*SCALE + pyx, base);
-                }
-            }
-        }
-    }
+pub const HEALTH_SIZE: (usize, usize) = (200, 12);
 
-    // jugador
-    let ux = off_x + (px as usize) * SCALE;
-    let uy = off_y + (py as usize) * SCALE;
-    for dy2 in 0..2 { for dx2 in 0..2 {
-        put_pixel(buf, ux+dx2, uy+dy2, rgb(255,255,255));
-    }}
+// Rectángulo lleno en unidades del tamaño base de la pieza (se escala con `a`).
+fn fill(buf: &mut [u32], a: &Area, x: f32, y: f32, w: f32, h: f32, c: u32) {
+    let (x0, y0) = (a.x + a.px(x), a.y + a.px(y));
+    let (x1, y1) = (a.x + a.px(x + w).max(a.px(x) + 1), a.y + a.px(y + h).max(a.px(y) + 1));
+    for yy in y0..y1 { for xx in x0..x1 { pset(buf, xx, yy, c); } }
+}
 
-    // dirección
-    let fx = (px + dx * 0.8) as usize * SCALE + off_x;
-    let fy = (py + dy * 0.8) as usize * SCALE + off_y;
-    super::render::line(buf, ux, uy, fx, fy, rgb(255,255,255));
+// Borde de 1 px alrededor de un rectángulo (en unidades base).
+fn outline(buf: &mut [u32], a: &Area, x: f32, y: f32, w: f32, h: f32, c: u32) {
+    let (x0, y0, x1, y1) = (a.x + a.px(x), a.y + a.px(y), a.x + a.px(x + w) - 1, a.y + a.px(y + h) - 1);
+    for xx in x0..=x1 { pset(buf, xx, y0, c); pset(buf, xx, y1, c); }
+    for yy in y0..=y1 { pset(buf, x0, yy, c); pset(buf, x1, yy, c); }
 }
 
-pub fn draw_health_bar(buf: &mut [u32], hp: i32, max_hp: i32) {
-    let w = 200usize;
-    let h = 12usize;
-    let x0 = 10usize;
-    let y0 = HEIGHT - h - 10;
+pub fn draw_health_bar(buf: &mut [u32], a: &Area, hp: i32, max_hp: i32) {
+    let (w, h) = (HEALTH_SIZE.0 as f32, HEALTH_SIZE.1 as f32);
     // fondo
-    for y in 0..h { for x in 0..w { put_pixel(buf, x0+x, y0+y, rgb(28,28,36)); } }
+    fill(buf, a, 0.0, 0.0, w, h, rgb(28,28,36));
     // relleno
     let pct = (hp.max(0) as f64) / (max_hp.max(1) as f64);
-    let fill = (pct * w as f64) as usize;
     let color = if pct > 0.5 { rgb(70, 220, 90) } else if pct > 0.25 { rgb(255, 200, 50) } else { rgb(230, 60, 60) };
-    for y in 0..h { for x in 0..fill { put_pixel(buf, x0+x, y0+y, color); } }
+    if pct > 0.0 { fill(buf, a, 0.0, 0.0, (pct * w as f64) as f32, h, color); }
     // borde
-    let c = rgb(255,255,255);
-    for x in 0..w { put_pixel(buf, x0+x, y0, c); put_pixel(buf, x0+x, y0+h-1, c); }
-    for y in 0..h { put_pixel(buf, x0, y0+y, c); put_pixel(buf, x0+w-1, y0+y, c); }
-}
-
-pub fn glyph5x7(ch: char) -> [u8; 7] {
-    // Cada byte es una fila (5 bits útiles: MSB a la izquierda). 1 = píxel encendido.
-    match ch.to_ascii_uppercase() {
-        'A' => [0x0E,0x11,0x11,0x1F,0x11,0x11,0x11],
-        'B' => [0x1E,0x11,0x11,0x1E,0x11,0x11,0x1E],
-        'C' => [0x0E,0x11,0x10,0x10,0x11,0x0E,0x00],
-        'D' => [0x1E,0x11,0x11,0x11,0x11,0x1E,0x00],
-        'E' => [0x1F,0x10,0x1E,0x10,0x10,0x1F,0x00],
-        'F' => [0x1F,0x10,0x1E,0x10,0x10,0x10,0x00],
-        'G' => [0x0E,0x11,0x10,0x17,0x11,0x0F,0x00],
-        'H' => [0x11,0x11,0x11,0x1F,0x11,0x11,0x11],
-        'I' => [0x0E,0x04,0x04,0x04,0x04,0x0E,0x00],
-        'J' => [0x07,0x01,0x01,0x01,0x11,0x0E,0x00],
-        'K' => [0x11,0x12,0x14,0x18,0x14,0x12,0x11],
-        'L' => [0x10,0x10,0x10,0x10,0x10,0x1F,0x00],
-        'M' => [0x11,0x1B,0x15,0x11,0x11,0x11,0x11],
-        'N' => [0x11,0x19,0x15,0x13,0x11,0x11,0x00],
-        'O' => [0x0E,0x11,0x11,0x11,0x11,0x0E,0x00],
-        'P' => [0x1E,0x11,0x11,0x1E,0x10,0x10,0x10],
-        'Q' => [0x0E,0x11,0x11,0x11,0x15,0x0E,0x01],
-        'R' => [0x1E,0x11,0x11,0x1E,0x14,0x12,0x11],
-        'S' => [0x0F,0x10,0x0E,0x01,0x01,0x1E,0x00],
-        'T' => [0x1F,0x04,0x04,0x04,0x04,0x04,0x00],
-        'U' => [0x11,0x11,0x11,0x11,0x11,0x0E,0x00],
-        'V' => [0x11,0x11,0x11,0x11,0x0A,0x04,0x00],
-        'W' => [0x11,0x11,0x11,0x15,0x15,0x0A,0x00],
-        'X' => [0x11,0x11,0x0A,0x04,0x0A,0x11,0x11],
-        'Y' => [0x11,0x11,0x0A,0x04,0x04,0x04,0x00],
-        'Z' => [0x1F,0x01,0x02,0x04,0x08,0x10,0x1F],
-        ' ' => [0x00,0x00,0x00,0x00,0x00,0x00,0x00],
-        _   => [0x1F,0x1F,0x1F,0x1F,0x1F,0x1F,0x00], // bloque si falta el glifo
+    outline(buf, a, 0.0, 0.0, w, h, rgb(255,255,255));
+}
+
+/// Tamaño base del inventario: una fila de íconos y, arriba, la de faltantes.
+pub fn inventory_size(inv: &Inventory, remaining: Option<usize>) -> (usize, usize) {
+    let items: usize = ITEM_KINDS.iter().map(|&k| inv.count(k) as usize).filter(|&n| n > 0).map(|n| 25 + n.min(10) * 4).sum();
+    (items.max(remaining.unwrap_or(0) * 10).max(1), 24)
+}
+
+/// Inventario: ícono por tipo + una marquita por unidad.
+/// `remaining` = objetos que faltan en el mapa (niveles de "recolectar todo").
+pub fn draw_inventory(buf: &mut [u32], a: &Area, inv: &Inventory, remaining: Option<usize>) {
+    let mut x = 0.0;
+    for kind in ITEM_KINDS {
+        let n = inv.count(kind) as usize;
+        if n == 0 { continue; }
+        // ícono 12x12 con borde
+        fill(buf, a, x, 12.0, 12.0, 12.0, kind.color());
+        outline(buf, a, x, 12.0, 12.0, 12.0, rgb(255,255,255));
+        x += 15.0;
+        for i in 0..n.min(10) { fill(buf, a, x + i as f32 * 4.0, 15.0, 2.0, 6.0, rgb(235,235,235)); }
+        x += n.min(10) as f32 * 4.0 + 10.0;
+    }
+
+    // Faltantes: puntitos huecos arriba del inventario
+    if let Some(left) = remaining {
+        for i in 0..left { outline(buf, a, i as f32 * 10.0, 0.0, 6.0, 6.0, rgb(255,230,120)); }
     }
 }
 
+/// Cronómetro: el tiempo de la corrida y, abajo, el récord de esta semilla.
+pub const TIMER_SIZE: (usize, usize) = (96, 30);
+
+pub fn draw_timer(buf: &mut [u32], a: &Area, time: f64, record: Option<f64>) {
+    let big = (2.0 * a.scale).round().max(1.0) as usize;
+    let small = a.scale.round().max(1.0) as usize;
+    let t = clock(time);
+    let right = |text: &str, scale: usize| a.x + a.w - text_width(text, scale) as i32;
+    draw_text(buf, &t, right(&t, big) + 2, a.y + 2, big, rgb(0, 0, 0));
+    draw_text(buf, &t, right(&t, big), a.y, big, rgb(255, 255, 255));
+    if let Some(r) = record {
+        let r = format!("récord {}", clock(r));
+        draw_text(buf, &r, right(&r, small), a.y + a.px(20.0), small, rgb(170, 200, 255));
+    }
+}
+
+// 83.456 → "1:23.45"
+fn clock(t: f64) -> String {
+    format!("{}:{:05.2}", (t / 60.0) as u32, t % 60.0)
+}
+
+/// Aviso corto en pantalla (además del título de la ventana).
+pub fn message_size(msg: &str) -> (usize, usize) { (text_width(msg, 2) + 16, 24) }
+
+/// Aviso con fondo oscurecido; `fade` (0..1) lo mezcla con lo que había debajo.
+pub fn draw_message(buf: &mut [u32], a: &Area, msg: &str, fade: f32) {
+    let scale = (2.0 * a.scale).round().max(1.0) as usize;
+    let (x0, y0) = (a.x.max(0), a.y.max(0));
+    let (x1, y1) = ((a.x + a.w).min(WIDTH as i32), (a.y + a.h).min(HEIGHT as i32));
+    if x0 >= x1 || y0 >= y1 { return; }
+    let under: Vec<u32> = (y0..y1).flat_map(|y| buf[y as usize * WIDTH + x0 as usize..y as usize * WIDTH + x1 as usize].to_vec()).collect();
+    for yy in y0..y1 {
+        for xx in x0..x1 {
+            let px = &mut buf[yy as usize * WIDTH + xx as usize];
+            *px = (*px >> 1) & 0x7F7F7F;
+        }
+    }
+    draw_text(buf, msg, a.x + a.px(8.0), a.y + a.px(4.0), scale, rgb(255, 240, 200));
+    if fade >= 1.0 { return; }
+    // Se apaga: vuelve a mezclar con el fondo que quedó guardado
+    let w = (x1 - x0) as usize;
+    for (i, &old) in under.iter().enumerate() {
+        let px = &mut buf[(y0 as usize + i / w) * WIDTH + x0 as usize + i % w];
+        *px = lerp(old, *px, fade.max(0.0));
+    }
+}
+
+/// Indicación centrada para lo que tienes enfrente (puerta, meta...): aviso con borde.
+pub fn draw_prompt(buf: &mut [u32], a: &Area, msg: &str, time: f64) {
+    draw_message(buf, a, msg, 1.0);
+    let (w, h) = message_size(msg);
+    let k = 0.75 + 0.25 * (time * 5.0).sin() as f32;
+    outline(buf, a, 0.0, 0.0, w as f32, h as f32, rgb((255.0 * k) as u8, (210.0 * k) as u8, (60.0 * k) as u8));
+}
+
+// Mezcla de dos colores (k = 0 da `a`, k = 1 da `b`).
+fn lerp(a: u32, b: u32, k: f32) -> u32 {
+    let ch = |s: u32| {
+        let (x, y) = (((a >> s) & 0xFF) as f32, ((b >> s) & 0xFF) as f32);
+        ((x + (y - x) * k) as u32) << s
+    };
+    ch(16) | ch(8) | ch(0)
+}
 
 fn pset(buf: &mut [u32], x: i32, y: i32, color: u32) {
     if x >= 0 && y >= 0 && (x as usize) < WIDTH && (y as usize) < HEIGHT {
@@ -99,36 +133,65 @@ fn pset(buf: &mut [u32], x: i32, y: i32, color: u32) {
     }
 }
 
-fn draw_char(buf: &mut [u32], ch: char, x: i32, y: i32, scale: usize, color: u32) {
-    let g = glyph5x7(ch.to_ascii_uppercase());
-    for (ry, row) in g.iter().enumerate() {
-        for rx in 0..5 {
-            if ((row >> (4 - rx)) & 1) == 1 {
-                for sy in 0..scale {
-                    for sx in 0..scale {
-                        pset(buf,
-                            x + (rx as i32 * scale as i32) + sx as i32,
-                            y + (ry as i32 * scale as i32) + sy as i32,
-                            color);
-                    }
-                }
+// Un glifo de la fuente en uso, cada píxel como un cuadrado de `scale`.
+fn draw_glyph(buf: &mut [u32], g: &Glyph, x: i32, y: i32, scale: usize, color: u32) {
+    let s = scale as i32;
+    for ry in 0..g.rows.len() {
+        for rx in 0..g.w {
+            if !g.on(rx, ry) { continue; }
+            let px = x + (g.left + rx as i32) * s;
+            let py = y + (g.top + ry as i32) * s;
+            for sy in 0..s {
+                for sx in 0..s { pset(buf, px + sx, py + sy, color); }
             }
         }
     }
 }
 
 pub fn draw_text(buf: &mut [u32], text: &str, x: i32, y: i32, scale: usize, color: u32) {
-    let mut cx = x;
-    for ch in text.chars() {
-        draw_char(buf, ch, cx, y, scale, color);
-        cx += ((5 + 1) * scale) as i32; // 1 columna de espacio
-    }
+    font::with(|f| {
+        let mut cx = x;
+        for ch in text.chars() {
+            let g = f.glyph(ch);
+            draw_glyph(buf, g, cx, y, scale, color);
+            cx += (g.advance * scale) as i32; // el avance ya trae el espacio
+        }
+    });
+}
+
+/// Ancho en píxeles de `text` (sin el espacio después del último glifo).
+pub fn text_width(text: &str, scale: usize) -> usize {
+    font::with(|f| text.chars().map(|c| f.glyph(c).advance).sum::<usize>().saturating_sub(1) * scale)
 }
 
 pub fn draw_text_centered(buf: &mut [u32], text: &str, y: usize, scale: usize, color: u32) {
-    let n = text.chars().count();
-    let cw = (5 + 1) * scale;
-    let total_w = n * cw - scale; // sin el último espacio
-    let start_x = (WIDTH.saturating_sub(total_w)) / 2;
+    let start_x = WIDTH.saturating_sub(text_width(text, scale)) / 2;
     draw_text(buf, text, start_x as i32, y as i32, scale, color);
 }
+
+/// Medalla chiquita (hexágono naranja con centro rojo) en un cuadro de `size`.
+pub fn draw_badge_icon(buf: &mut [u32], x0: usize, y0: usize, size: usize) {
+    let r = (size / 2) as i32;
+    for y in 0..size as i32 {
+        for x in 0..size as i32 {
+            let (dx, dy) = ((x - r).abs(), (y - r).abs());
+            if dx + dy / 2 > r || dy > r { continue; }
+            let c = if dx + dy <= r / 3 { rgb(220, 40, 30) } else { rgb(255, 170, 40) };
+            put_pixel(buf, x0 + x as usize, y0 + y as usize, c);
+        }
+    }
+}
+
+/// Candado gris: arco arriba y cuerpo abajo.
+pub fn draw_lock_icon(buf: &mut [u32], x0: usize, y0: usize, size: usize) {
+    let s = size as i32;
+    for y in 0..s {
+        for x in 0..s {
+            let body = y >= s / 2 && x >= s / 8 && x < s - s / 8;
+            let (dx, dy) = (x - s / 2, y - s / 2);
+            let d2 = dx * dx + dy * dy;
+            let arc = y < s / 2 && d2 <= (s * 3 / 8).pow(2) && d2 >= (s / 4).pow(2);
+            if body || arc { put_pixel(buf, x0 + x as usize, y0 + y as usize, rgb(150, 150, 160)); }
+        }
+    }
+}
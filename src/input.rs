@@ -0,0 +1,436 @@
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode, Window};
+
+pub const BINDINGS_PATH: &str = "input.cfg";
+
+/// Acciones del juego. Todo el código lee acciones, nunca teclas sueltas
+/// (salvo la captura de tecla al reasignar en el menú de ajustes).
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Action {
+    Forward, Back, StrafeLeft, StrafeRight, TurnLeft, TurnRight, Sprint,
+    Potion, Throw, Attack, Pause, Mute, QuickSave, QuickLoad, FreeCam, Debug,
+    // minimapa
+    Automap, MapZoomIn, MapZoomOut, MapRotate,
+    // navegación de menús
+    Up, Down, Left, Right, Confirm, Cancel,
+}
+
+pub const ACTIONS: [Action; 26] = [
+    Action::Forward, Action::Back, Action::StrafeLeft, Action::StrafeRight,
+    Action::TurnLeft, Action::TurnRight, Action::Potion, Action::Throw, Action::Attack,
+    Action::Pause, Action::Mute, Action::QuickSave, Action::QuickLoad, Action::FreeCam,
+    Action::Up, Action::Down, Action::Left, Action::Right, Action::Confirm, Action::Cancel,
+    // las nuevas van al final: la posición es el bit en las repeticiones
+    Action::Debug, Action::Sprint, Action::Automap, Action::MapZoomIn, Action::MapZoomOut, Action::MapRotate,
+];
+
+/// Las que se pueden reasignar desde la pausa (el resto, editando `input.cfg`).
+pub const REBINDABLE: [Action; 12] = [
+    Action::Forward, Action::Back, Action::StrafeLeft, Action::StrafeRight,
+    Action::TurnLeft, Action::TurnRight, Action::Sprint, Action::Potion, Action::Throw, Action::Attack,
+    Action::Pause, Action::Mute,
+];
+
+impl Action {
+    fn index(self) -> usize { ACTIONS.iter().position(|a| *a == self).unwrap_or(0) }
+
+    /// Nombre en `input.cfg`.
+    pub fn id(self) -> &'static str {
+        match self {
+            Action::Forward => "forward", Action::Back => "back",
+            Action::StrafeLeft => "strafe_left", Action::StrafeRight => "strafe_right",
+            Action::TurnLeft => "turn_left", Action::TurnRight => "turn_right", Action::Sprint => "sprint",
+            Action::Potion => "potion", Action::Throw => "throw", Action::Attack => "attack",
+            Action::Pause => "pause", Action::Mute => "mute",
+            Action::QuickSave => "quicksave", Action::QuickLoad => "quickload",
+            Action::FreeCam => "free_cam", Action::Debug => "debug",
+            Action::Automap => "automap", Action::MapZoomIn => "map_zoom_in",
+            Action::MapZoomOut => "map_zoom_out", Action::MapRotate => "map_rotate",
+            Action::Up => "menu_up", Action::Down => "menu_down",
+            Action::Left => "menu_left", Action::Right => "menu_right",
+            Action::Confirm => "confirm", Action::Cancel => "cancel",
+        }
+    }
+
+    /// Texto en el menú de ajustes.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Forward => "AVANZAR", Action::Back => "RETROCEDER",
+            Action::StrafeLeft => "PASO IZQ", Action::StrafeRight => "PASO DER",
+            Action::TurnLeft => "GIRAR IZQ", Action::TurnRight => "GIRAR DER", Action::Sprint => "CORRER",
+            Action::Potion => "POCION", Action::Throw => "POKEBOLA", Action::Attack => "ATACAR",
+            Action::Pause => "PAUSA", Action::Mute => "SILENCIO",
+            Action::QuickSave => "GUARDAR", Action::QuickLoad => "CARGAR",
+            Action::FreeCam => "CAMARA LIBRE", Action::Debug => "DEBUG",
+            Action::Automap => "MAPA", Action::MapZoomIn => "ACERCAR MAPA",
+            Action::MapZoomOut => "ALEJAR MAPA", Action::MapRotate => "GIRAR MAPA",
+            Action::Up => "ARRIBA", Action::Down => "ABAJO", Action::Left => "IZQUIERDA",
+            Action::Right => "DERECHA", Action::Confirm => "ACEPTAR", Action::Cancel => "CANCELAR",
+        }
+    }
+
+    fn defaults(self) -> (&'static [Key], &'static [PadInput]) {
+        use PadInput::*;
+        match self {
+            Action::Forward => (&[Key::W], &[AxisNeg(1)]),
+            Action::Back => (&[Key::S], &[AxisPos(1)]),
+            Action::StrafeLeft => (&[Key::A], &[AxisNeg(0)]),
+            Action::StrafeRight => (&[Key::D], &[AxisPos(0)]),
+            Action::TurnLeft => (&[Key::Left], &[AxisNeg(3)]),
+            Action::TurnRight => (&[Key::Right], &[AxisPos(3)]),
+            Action::Sprint => (&[Key::LeftShift], &[Button(9)]),
+            Action::Potion => (&[Key::E], &[Button(3)]),
+            Action::Throw => (&[Key::F], &[Button(5)]),
+            Action::Attack => (&[Key::Q], &[Button(2)]),
+            Action::Pause => (&[Key::Escape], &[Button(7)]),
+            Action::Mute => (&[Key::M], &[Button(6)]),
+            Action::QuickSave => (&[Key::F5], &[]),
+            Action::QuickLoad => (&[Key::F9], &[]),
+            Action::FreeCam => (&[Key::C], &[Button(4)]),
+            Action::Debug => (&[Key::F3], &[]),
+            Action::Automap => (&[Key::Tab], &[Button(8)]),
+            Action::MapZoomIn => (&[Key::Equal, Key::NumPadPlus], &[]),
+            Action::MapZoomOut => (&[Key::Minus, Key::NumPadMinus], &[]),
+            Action::MapRotate => (&[Key::N], &[]),
+            Action::Up => (&[Key::Up], &[AxisNeg(7)]),
+            Action::Down => (&[Key::Down], &[AxisPos(7)]),
+            Action::Left => (&[Key::Left], &[AxisNeg(6)]),
+            Action::Right => (&[Key::Right], &[AxisPos(6)]),
+            Action::Confirm => (&[Key::Enter, Key::Space], &[Button(0)]),
+            Action::Cancel => (&[Key::Backspace], &[Button(1)]),
+        }
+    }
+}
+
+// ===================== Nombres de teclas / botones =====================
+
+// Teclas con nombre (para el archivo y el menú). Las que no están no se pueden asignar.
+const KEY_NAMES: &[(Key, &str)] = &[
+    (Key::A, "A"), (Key::B, "B"), (Key::C, "C"), (Key::D, "D"), (Key::E, "E"), (Key::F, "F"),
+    (Key::G, "G"), (Key::H, "H"), (Key::I, "I"), (Key::J, "J"), (Key::K, "K"), (Key::L, "L"),
+    (Key::M, "M"), (Key::N, "N"), (Key::O, "O"), (Key::P, "P"), (Key::Q, "Q"), (Key::R, "R"),
+    (Key::S, "S"), (Key::T, "T"), (Key::U, "U"), (Key::V, "V"), (Key::W, "W"), (Key::X, "X"),
+    (Key::Y, "Y"), (Key::Z, "Z"),
+    (Key::Up, "UP"), (Key::Down, "DOWN"), (Key::Left, "LEFT"), (Key::Right, "RIGHT"),
+    (Key::Space, "SPACE"), (Key::Tab, "TAB"), (Key::LeftShift, "LSHIFT"), (Key::RightShift, "RSHIFT"),
+    (Key::LeftCtrl, "LCTRL"), (Key::RightCtrl, "RCTRL"), (Key::LeftAlt, "LALT"),
+    (Key::Enter, "ENTER"), (Key::Escape, "ESCAPE"), (Key::Backspace, "BACKSPACE"),
+    (Key::F3, "F3"), (Key::F5, "F5"), (Key::F9, "F9"),
+    (Key::Equal, "EQUAL"), (Key::Minus, "MINUS"), (Key::NumPadPlus, "NUMPLUS"), (Key::NumPadMinus, "NUMMINUS"),
+];
+
+pub fn key_name(key: Key) -> Option<&'static str> {
+    KEY_NAMES.iter().find(|(k, _)| *k == key).map(|(_, n)| *n)
+}
+
+fn key_from_name(name: &str) -> Option<Key> {
+    KEY_NAMES.iter().find(|(_, n)| n.eq_ignore_ascii_case(name)).map(|(k, _)| *k)
+}
+
+/// Entrada de gamepad: botón N, o eje N pasado del umbral hacia + / -.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PadInput { Button(u8), AxisPos(u8), AxisNeg(u8) }
+
+impl PadInput {
+    // `pad:b0`, `pad:a1+`, `pad:a1-`
+    fn name(self) -> String {
+        match self {
+            PadInput::Button(n) => format!("pad:b{n}"),
+            PadInput::AxisPos(n) => format!("pad:a{n}+"),
+            PadInput::AxisNeg(n) => format!("pad:a{n}-"),
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.strip_prefix("pad:")?;
+        if let Some(b) = s.strip_prefix('b') { return Some(PadInput::Button(b.parse().ok()?)); }
+        let a = s.strip_prefix('a')?;
+        if let Some(n) = a.strip_suffix('+') { return Some(PadInput::AxisPos(n.parse().ok()?)); }
+        Some(PadInput::AxisNeg(a.strip_suffix('-')?.parse().ok()?))
+    }
+}
+
+// ===================== Bindings (input.cfg) =====================
+
+/// Qué teclas y entradas del gamepad disparan cada acción.
+/// La primera tecla de la lista es la "principal" (la que muestra y cambia el menú).
+pub struct Bindings {
+    keys: Vec<Vec<Key>>,
+    pad: Vec<Vec<PadInput>>,
+}
+
+impl Bindings {
+    fn defaults() -> Self {
+        Self {
+            keys: ACTIONS.iter().map(|a| a.defaults().0.to_vec()).collect(),
+            pad: ACTIONS.iter().map(|a| a.defaults().1.to_vec()).collect(),
+        }
+    }
+
+    /// Lee `input.cfg` (`accion = TECLA, TECLA, pad:b0`). Las acciones que falten
+    /// quedan con sus valores por defecto, y si una línea no nombra nada del pad
+    /// se conservan los botones de fábrica. Si el archivo no existe se crea.
+    pub fn load() -> Self {
+        let mut b = Self::defaults();
+        let Ok(text) = std::fs::read_to_string(BINDINGS_PATH) else {
+            b.save();
+            return b;
+        };
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let Some((id, list)) = line.split_once('=') else { continue; };
+            let Some(a) = ACTIONS.into_iter().find(|a| a.id() == id.trim()) else {
+                eprintln!("{BINDINGS_PATH}: acción desconocida '{}'", id.trim());
+                continue;
+            };
+            let (mut keys, mut pad) = (Vec::new(), Vec::new());
+            for tok in list.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                if let Some(p) = PadInput::parse(tok) { pad.push(p); }
+                else if let Some(k) = key_from_name(tok) { keys.push(k); }
+                else { eprintln!("{BINDINGS_PATH}: no entiendo '{tok}' en {id}"); }
+            }
+            b.keys[a.index()] = keys;
+            if !pad.is_empty() { b.pad[a.index()] = pad; }
+        }
+        b
+    }
+
+    pub fn save(&self) {
+        let mut out = String::from("# accion = teclas y botones del gamepad (pad:bN boton, pad:aN+ / pad:aN- eje)\n");
+        for a in ACTIONS {
+            let names: Vec<String> = self.keys[a.index()].iter().filter_map(|k| key_name(*k).map(str::to_string))
+                .chain(self.pad[a.index()].iter().map(|p| p.name()))
+                .collect();
+            out.push_str(&format!("{} = {}\n", a.id(), names.join(", ")));
+        }
+        if let Err(e) = std::fs::write(BINDINGS_PATH, out) {
+            eprintln!("No se pudo guardar {BINDINGS_PATH}: {e}");
+        }
+    }
+
+    pub fn primary(&self, a: Action) -> Option<Key> { self.keys[a.index()].first().copied() }
+
+    /// La acción de juego fija (guardar, F3, automapa...) que ya usa `key`: esas no se
+    /// pisan desde la pausa. Las de menú no cuentan (no se usan mientras se juega).
+    pub fn reserved_by(&self, key: Key) -> Option<Action> {
+        const MENU: [Action; 6] = [Action::Up, Action::Down, Action::Left, Action::Right, Action::Confirm, Action::Cancel];
+        ACTIONS.into_iter()
+            .filter(|a| !REBINDABLE.contains(a) && !MENU.contains(a))
+            .find(|a| self.keys[a.index()].contains(&key))
+    }
+
+    /// Cambia la tecla principal de `a`; si otra acción de juego la tenía, se la cede.
+    pub fn set_primary(&mut self, a: Action, key: Key) {
+        let old = self.primary(a);
+        for other in REBINDABLE.into_iter().filter(|o| *o != a) {
+            let ks = &mut self.keys[other.index()];
+            if let Some(slot) = ks.iter_mut().find(|k| **k == key) {
+                match old { Some(o) => *slot = o, None => { ks.retain(|k| *k != key); } }
+                break;
+            }
+        }
+        let ks = &mut self.keys[a.index()];
+        ks.retain(|k| *k != key);
+        if ks.is_empty() { ks.push(key); } else { ks[0] = key; }
+    }
+}
+
+// ===================== Estado por frame =====================
+
+/// Aviso de conexión/desconexión del gamepad.
+pub enum PadEvent { Connected(String), Disconnected }
+
+/// Entrada de un tick de simulación: lo apretado, lo recién apretado desde el
+/// tick anterior y el giro del mouse (radianes) en ese lapso. Es lo que se graba
+/// en las repeticiones.
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct TickInput {
+    pub down: u32,
+    pub pressed: u32,
+    pub turn: f32,
+}
+
+impl TickInput {
+    pub fn down(&self, a: Action) -> bool { self.down & (1 << a.index()) != 0 }
+    pub fn pressed(&self, a: Action) -> bool { self.pressed & (1 << a.index()) != 0 }
+}
+
+pub struct Input {
+    pub bindings: Bindings,
+    down: Vec<bool>,
+    prev: Vec<bool>,
+    latched: Vec<bool>, // apretadas desde el último `take_tick` (puede haber frames sin tick)
+    mouse_dx: f32,
+    last_mouse_x: Option<f32>,
+    keys_pressed: Vec<Key>,
+    mouse_pos: Option<(f32, f32)>, // dentro de la ventana (para los menús)
+    mouse_down: bool,
+    clicked: bool,                 // botón izquierdo recién apretado
+    pad: Option<pad::Gamepad>,
+    scan_timer: f64,
+}
+
+const PAD_SCAN_EVERY: f64 = 1.0; // s entre búsquedas de gamepad (hot-plug)
+const AXIS_THRESHOLD: i16 = 16000;
+
+impl Input {
+    pub fn new() -> Self {
+        let n = ACTIONS.len();
+        Self { bindings: Bindings::load(), down: vec![false; n], prev: vec![false; n],
+               latched: vec![false; n], mouse_dx: 0.0, last_mouse_x: None,
+               keys_pressed: Vec::new(), mouse_pos: None, mouse_down: false, clicked: false,
+               pad: None, scan_timer: 0.0 }
+    }
+
+    /// Lee teclado y gamepad una vez por frame. Devuelve un aviso si el gamepad se
+    /// conectó o desconectó.
+    pub fn update(&mut self, window: &Window, dt: f64) -> Option<PadEvent> {
+        let mut event = None;
+
+        // Gamepad: leer eventos o buscar uno nuevo cada tanto
+        match self.pad.as_mut() {
+            Some(p) => if !p.poll() {
+                self.pad = None;
+                event = Some(PadEvent::Disconnected);
+            },
+            None => {
+                self.scan_timer -= dt;
+                if self.scan_timer <= 0.0 {
+                    self.scan_timer = PAD_SCAN_EVERY;
+                    self.pad = pad::Gamepad::find();
+                    if let Some(p) = &self.pad { event = Some(PadEvent::Connected(p.name.clone())); }
+                }
+            }
+        }
+
+        std::mem::swap(&mut self.prev, &mut self.down);
+        for a in ACTIONS {
+            let i = a.index();
+            let key = self.bindings.keys[i].iter().any(|k| window.is_key_down(*k));
+            let pad = self.pad.as_ref().is_some_and(|p| self.bindings.pad[i].iter().any(|b| p.is_down(*b)));
+            self.down[i] = key || pad;
+            if self.down[i] && !self.prev[i] { self.latched[i] = true; }
+        }
+        self.keys_pressed = window.get_keys_pressed(KeyRepeat::No);
+
+        if let Some((mx, _)) = window.get_mouse_pos(MouseMode::Pass) {
+            if let Some(prev_x) = self.last_mouse_x { self.mouse_dx += mx - prev_x; }
+            self.last_mouse_x = Some(mx);
+        }
+        self.mouse_pos = window.get_mouse_pos(MouseMode::Discard);
+        let down = window.get_mouse_down(MouseButton::Left);
+        self.clicked = down && !self.mouse_down;
+        self.mouse_down = down;
+        event
+    }
+
+    /// Entrada para el siguiente tick de simulación (y la descarta: lo recién
+    /// apretado y el mouse se cuentan en un solo tick). `sens`: rad por pixel.
+    pub fn take_tick(&mut self, sens: f64) -> TickInput {
+        let turn = (std::mem::take(&mut self.mouse_dx) as f64 * sens) as f32;
+        let mut t = TickInput { turn, ..Default::default() };
+        for a in ACTIONS {
+            let i = a.index();
+            if self.down[i] { t.down |= 1 << i; }
+            if std::mem::take(&mut self.latched[i]) { t.pressed |= 1 << i; }
+        }
+        t
+    }
+
+    /// Descarta lo apretado este frame y lo pendiente para la simulación (transiciones).
+    /// Lo que siga apretado no vuelve a contar como recién apretado hasta soltarlo.
+    pub fn block(&mut self) {
+        self.prev.copy_from_slice(&self.down);
+        self.latched.fill(false);
+        self.keys_pressed.clear();
+        self.mouse_dx = 0.0;
+        self.clicked = false;
+    }
+
+    pub fn down(&self, a: Action) -> bool { self.down[a.index()] }
+
+    /// Recién apretada este frame (flanco de subida).
+    pub fn pressed(&self, a: Action) -> bool { self.down[a.index()] && !self.prev[a.index()] }
+
+    /// Teclas crudas apretadas este frame (para capturar una tecla al reasignar).
+    pub fn keys_pressed(&self) -> &[Key] { &self.keys_pressed }
+
+    /// Puntero en píxeles de la ventana (`None` si está afuera).
+    pub fn mouse_pos(&self) -> Option<(f32, f32)> { self.mouse_pos }
+
+    /// Click izquierdo este frame.
+    pub fn clicked(&self) -> bool { self.clicked }
+}
+
+// ===================== Backend de gamepad =====================
+
+// Linux: API de joystick del kernel (/dev/input/jsN), sin dependencias.
+// Cada evento son 8 bytes: u32 tiempo, i16 valor, u8 tipo (1 botón, 2 eje, |0x80 inicial), u8 número.
+#[cfg(target_os = "linux")]
+mod pad {
+    use super::{PadInput, AXIS_THRESHOLD};
+    use std::fs::File;
+    use std::io::{ErrorKind, Read};
+    use std::os::unix::fs::OpenOptionsExt;
+
+    const O_NONBLOCK: i32 = 0o4000;
+
+    pub struct Gamepad {
+        file: File,
+        pub name: String,
+        buttons: [bool; 32],
+        axes: [i16; 16],
+    }
+
+    impl Gamepad {
+        pub fn find() -> Option<Self> {
+            (0..4).find_map(|i| {
+                let path = format!("/dev/input/js{i}");
+                let file = std::fs::OpenOptions::new().read(true).custom_flags(O_NONBLOCK).open(&path).ok()?;
+                Some(Self { file, name: path, buttons: [false; 32], axes: [0; 16] })
+            })
+        }
+
+        /// Vacía la cola de eventos; false si el dispositivo desapareció.
+        pub fn poll(&mut self) -> bool {
+            let mut ev = [0u8; 8];
+            loop {
+                match self.file.read(&mut ev) {
+                    Ok(8) => {
+                        let value = i16::from_le_bytes([ev[4], ev[5]]);
+                        let (kind, n) = (ev[6] & !0x80, ev[7] as usize);
+                        match kind {
+                            1 if n < self.buttons.len() => self.buttons[n] = value != 0,
+                            2 if n < self.axes.len() => self.axes[n] = value,
+                            _ => {}
+                        }
+                    }
+                    Ok(_) => return false, // EOF / lectura corta: se desconectó
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => return true,
+                    Err(_) => return false,
+                }
+            }
+        }
+
+        pub fn is_down(&self, b: PadInput) -> bool {
+            let axis = |n: u8| self.axes.get(n as usize).copied().unwrap_or(0);
+            match b {
+                PadInput::Button(n) => self.buttons.get(n as usize).copied().unwrap_or(false),
+                PadInput::AxisPos(n) => axis(n) > AXIS_THRESHOLD,
+                PadInput::AxisNeg(n) => axis(n) < -AXIS_THRESHOLD,
+            }
+        }
+    }
+}
+
+// Otras plataformas: sin gamepad por ahora (todo sigue andando con teclado).
+#[cfg(not(target_os = "linux"))]
+mod pad {
+    use super::PadInput;
+
+    pub struct Gamepad { pub name: String }
+
+    impl Gamepad {
+        pub fn find() -> Option<Self> { None }
+        pub fn poll(&mut self) -> bool { false }
+        pub fn is_down(&self, _b: PadInput) -> bool { false }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::constants::rgb;
+use crate::sprites::{AnimatedSprite, Fallback, SpriteFrame, SpriteSheet};
+use std::rc::Rc;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ItemKind { Potion, Key, Badge, Pokeball }
+
+pub const ITEM_KINDS: [ItemKind; 4] = [ItemKind::Potion, ItemKind::Key, ItemKind::Badge, ItemKind::Pokeball];
+
+pub const POTION_HEAL: i32 = 40;
+
+impl ItemKind {
+    fn index(self) -> usize {
+        match self { ItemKind::Potion => 0, ItemKind::Key => 1, ItemKind::Badge => 2, ItemKind::Pokeball => 3 }
+    }
+
+    /// Nombre en archivos de guardado.
+    pub fn id(self) -> &'static str {
+        match self { ItemKind::Potion => "potion", ItemKind::Key => "key", ItemKind::Badge => "badge", ItemKind::Pokeball => "pokeball" }
+    }
+    pub fn from_id(id: &str) -> Option<Self> { ITEM_KINDS.into_iter().find(|k| k.id() == id) }
+
+    /// Nombre para los avisos en pantalla.
+    pub fn name(self) -> &'static str {
+        match self { ItemKind::Potion => "Poción", ItemKind::Key => "Llave", ItemKind::Badge => "Medalla", ItemKind::Pokeball => "Pokébola" }
+    }
+
+    /// Color del ícono en HUD/minimapa.
+    pub fn color(self) -> u32 {
+        match self {
+            ItemKind::Potion => rgb(190, 90, 230),
+            ItemKind::Key => rgb(250, 210, 60),
+            ItemKind::Badge => rgb(255, 170, 40),
+            ItemKind::Pokeball => rgb(230, 60, 60),
+        }
+    }
+
+    // Nombre de la hoja en assets/ y su fallback procedural
+    fn sheet(self) -> (&'static str, Fallback) {
+        match self {
+            ItemKind::Potion => ("potion", Fallback::Frames(gen_potion_frames, 4.0)),
+            ItemKind::Key => ("key", Fallback::Frames(gen_key_frames, 4.0)),
+            ItemKind::Badge => ("badge", Fallback::Frames(gen_badge_frames, 4.0)),
+            ItemKind::Pokeball => ("pokeball", Fallback::Frames(crate::sprites::pokeball_fallback, 6.0)),
+        }
+    }
+}
+
+// ===================== Inventario =====================
+
+#[derive(Clone, Default)]
+pub struct Inventory {
+    counts: [u32; 4],
+}
+
+impl Inventory {
+    pub fn add(&mut self, kind: ItemKind) { self.counts[kind.index()] += 1; }
+    pub fn count(&self, kind: ItemKind) -> u32 { self.counts[kind.index()] }
+
+    /// Consume uno si hay; devuelve si se pudo.
+    pub fn take(&mut self, kind: ItemKind) -> bool {
+        let c = &mut self.counts[kind.index()];
+        if *c == 0 { return false; }
+        *c -= 1;
+        true
+    }
+}
+
+// ===================== Objetos en el mundo =====================
+
+pub struct Pickup {
+    pub kind: ItemKind,
+    pub sprite: AnimatedSprite,
+    base_z: f64,
+    phase: f64,
+}
+
+impl Pickup {
+    pub fn new(kind: ItemKind, x: f64, y: f64, sheet: Rc<SpriteSheet>) -> Self {
+        let mut sprite = AnimatedSprite::new(x, y, sheet);
+        sprite.play("idle");
+        sprite.scale = 0.35;
+        sprite.z = 0.12;
+        Self { kind, sprite, base_z: 0.12, phase: x + y }
+    }
+
+    pub fn update(&mut self, dt: f64) {
+        self.sprite.update(dt);
+        // Flota suavecito para que se note que se puede agarrar
+        self.phase += dt * 3.0;
+        self.sprite.z = self.base_z + 0.05 * self.phase.sin();
+    }
+}
+
+/// Crea los pickups (una hoja compartida por tipo).
+pub fn spawn(defs: &[(ItemKind, f64, f64)]) -> Vec<Pickup> {
+    let mut sheets: Vec<(ItemKind, Rc<SpriteSheet>)> = Vec::new();
+    let mut out = Vec::new();
+    for &(kind, x, y) in defs {
+        let sheet = match sheets.iter().find(|(k, _)| *k == kind) {
+            Some((_, s)) => s.clone(),
+            None => {
+                let (name, fallback) = kind.sheet();
+                let s = Rc::new(SpriteSheet::load(name, fallback));
+                sheets.push((kind, s.clone()));
+                s
+            }
+        };
+        out.push(Pickup::new(kind, x, y, sheet));
+    }
+    out
+}
+
+// ===================== Frames procedurales =====================
+
+const ITEM_SIZE: usize = 48;
+
+// Dibuja con una función f(x, y, frame) -> Option<color>; None = transparente.
+fn gen_frames(n: usize, f: impl Fn(i32, i32, usize) -> Option<u32>) -> Vec<SpriteFrame> {
+    (0..n).map(|fr| {
+        let mut rgba = vec![0u32; ITEM_SIZE * ITEM_SIZE];
+        for y in 0..ITEM_SIZE {
+            for x in 0..ITEM_SIZE {
+                if let Some(c) = f(x as i32, y as i32, fr) {
+                    rgba[y * ITEM_SIZE + x] = 0xFF00_0000 | c;
+                }
+            }
+        }
+        SpriteFrame { w: ITEM_SIZE, h: ITEM_SIZE, rgba }
+    }).collect()
+}
+
+// Frasco morado con corcho; el brillo sube y baja.
+fn gen_potion_frames() -> Vec<SpriteFrame> {
+    gen_frames(4, |x, y, fr| {
+        let (cx, cy) = (24, 30);
+        let (dx, dy) = (x - cx, y - cy);
+        if dx * dx + dy * dy <= 14 * 14 {
+            let shine = 22 + (fr as i32) * 3;
+            if (x - 18).abs() <= 2 && (y - shine).abs() <= 3 { return Some(rgb(245, 220, 255)); }
+            return Some(if y < 26 { rgb(150, 70, 200) } else { rgb(190, 90, 230) });
+        }
+        if (x - cx).abs() <= 4 && (10..17).contains(&y) { return Some(rgb(200, 200, 220)); } // cuello
+        if (x - cx).abs() <= 5 && (5..10).contains(&y) { return Some(rgb(140, 90, 50)); }    // corcho
+        None
+    })
+}
+
+// Llave dorada; destello que recorre el mango.
+fn gen_key_frames() -> Vec<SpriteFrame> {
+    gen_frames(4, |x, y, fr| {
+        let gold = rgb(250, 210, 60);
+        let (dx, dy) = (x - 14, y - 24);
+        let d2 = dx * dx + dy * dy;
+        let spark_x = 10 + fr as i32 * 8;
+        let spark = (x - spark_x).abs() <= 1 && (y - 24).abs() <= 3;
+        if (36..=81).contains(&d2) { return Some(if spark { rgb(255, 255, 220) } else { gold }); } // aro
+        if (22..44).contains(&x) && (y - 24).abs() <= 2 { return Some(if spark { rgb(255, 255, 220) } else { gold }); }
+        if (x == 36 || x == 37 || x == 41 || x == 42) && (27..33).contains(&y) { return Some(gold); } // dientes
+        None
+    })
+}
+
+// Medalla: hexágono naranja con centro rojo, rota su brillo.
+fn gen_badge_frames() -> Vec<SpriteFrame> {
+    gen_frames(4, |x, y, fr| {
+        let (dx, dy) = ((x - 24).abs(), (y - 24).abs());
+        if dy > 18 || dx > 18 || dx + dy / 2 > 20 { return None; }
+        if dx + dy <= 7 { return Some(rgb(220, 40, 30)); }
+        let lit = match fr { 0 => x < 24 && y < 24, 1 => x >= 24 && y < 24, 2 => x >= 24 && y >= 24, _ => x < 24 && y >= 24 };
+        Some(if lit { rgb(255, 215, 120) } else { rgb(255, 170, 40) })
+    })
+}
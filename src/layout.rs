@@ -0,0 +1,139 @@
+use std::path::Path;
+
+pub const HUD_PATH: &str = "assets/hud.cfg";
+
+/// Desde dónde se mide una pieza del HUD: esquina, borde o centro de la pantalla.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Anchor { TopLeft, Top, TopRight, Left, Center, Right, BottomLeft, Bottom, BottomRight }
+
+impl Anchor {
+    fn from_id(id: &str) -> Option<Self> {
+        use Anchor::*;
+        Some(match id {
+            "top_left" => TopLeft, "top" => Top, "top_right" => TopRight,
+            "left" => Left, "center" => Center, "right" => Right,
+            "bottom_left" => BottomLeft, "bottom" => Bottom, "bottom_right" => BottomRight,
+            _ => return None,
+        })
+    }
+
+    // 0 = pegado al principio, 1 = centrado, 2 = pegado al final (en x y en y)
+    fn sides(self) -> (u8, u8) {
+        use Anchor::*;
+        match self {
+            TopLeft => (0, 0), Top => (1, 0), TopRight => (2, 0),
+            Left => (0, 1), Center => (1, 1), Right => (2, 1),
+            BottomLeft => (0, 2), Bottom => (1, 2), BottomRight => (2, 2),
+        }
+    }
+}
+
+/// Piezas del HUD que se pueden acomodar.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Widget { Health, Inventory, Minimap, Timer, Messages, Prompt }
+
+pub const WIDGETS: [Widget; 6] = [Widget::Health, Widget::Inventory, Widget::Minimap, Widget::Timer, Widget::Messages, Widget::Prompt];
+
+impl Widget {
+    fn index(self) -> usize { WIDGETS.iter().position(|w| *w == self).unwrap_or(0) }
+
+    /// Nombre en `assets/hud.cfg`.
+    fn id(self) -> &'static str {
+        match self {
+            Widget::Health => "health", Widget::Inventory => "inventory", Widget::Minimap => "minimap",
+            Widget::Timer => "timer", Widget::Messages => "messages", Widget::Prompt => "prompt",
+        }
+    }
+}
+
+/// Dónde va una pieza: anclaje, margen desde ese borde (hacia adentro) y escala.
+#[derive(Copy, Clone)]
+pub struct Slot {
+    pub anchor: Anchor,
+    pub margin: (i32, i32),
+    pub scale: f32,
+    pub visible: bool,
+}
+
+/// Lugar ya calculado para dibujar una pieza este frame (en píxeles, escala incluida).
+#[derive(Copy, Clone)]
+pub struct Area {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+    pub scale: f32,
+}
+
+impl Area {
+    /// Pasa una medida del tamaño base de la pieza a píxeles.
+    pub fn px(&self, v: f32) -> i32 { (v * self.scale).round() as i32 }
+}
+
+/// Posiciones del HUD (`assets/hud.cfg`, opcional). Una línea por pieza:
+///   health  bottom_left  10 10  1
+/// (pieza, anclaje, margen x, margen y, escala; `off` en vez del anclaje la esconde).
+pub struct HudLayout {
+    slots: [Slot; WIDGETS.len()],
+}
+
+impl HudLayout {
+    pub fn load() -> Self {
+        let mut l = Self::default();
+        let Ok(text) = std::fs::read_to_string(HUD_PATH) else { return l; };
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let w: Vec<&str> = line.split_whitespace().collect();
+            let Some(widget) = WIDGETS.into_iter().find(|x| x.id() == w[0]) else {
+                eprintln!("{HUD_PATH}: pieza desconocida '{}'", w[0]);
+                continue;
+            };
+            let slot = &mut l.slots[widget.index()];
+            if w.get(1) == Some(&"off") { slot.visible = false; continue; }
+            let (Some(anchor), Some(mx), Some(my)) = (
+                w.get(1).and_then(|a| Anchor::from_id(a)),
+                w.get(2).and_then(|v| v.parse().ok()),
+                w.get(3).and_then(|v| v.parse().ok()),
+            ) else {
+                eprintln!("{HUD_PATH}: no entiendo '{line}'");
+                continue;
+            };
+            let scale = w.get(4).and_then(|v| v.parse::<f32>().ok()).unwrap_or(1.0);
+            *slot = Slot { anchor, margin: (mx, my), scale: scale.clamp(0.25, 4.0), visible: true };
+        }
+        l
+    }
+
+    pub fn uses(path: &Path) -> bool { path == Path::new(HUD_PATH) }
+
+    pub fn slot(&self, w: Widget) -> Slot { self.slots[w.index()] }
+
+    /// Dónde dibujar `w` con tamaño base `size` en una pantalla de `screen` píxeles
+    /// (`None` si está escondida).
+    pub fn place(&self, w: Widget, size: (usize, usize), screen: (usize, usize)) -> Option<Area> {
+        let s = self.slot(w);
+        if !s.visible { return None; }
+        let (pw, ph) = ((size.0 as f32 * s.scale).round() as i32, (size.1 as f32 * s.scale).round() as i32);
+        let (sx, sy) = s.anchor.sides();
+        let along = |side: u8, margin: i32, len: i32, total: usize| match side {
+            0 => margin,
+            1 => (total as i32 - len) / 2 + margin,
+            _ => total as i32 - len - margin,
+        };
+        Some(Area { x: along(sx, s.margin.0, pw, screen.0), y: along(sy, s.margin.1, ph, screen.1), w: pw, h: ph, scale: s.scale })
+    }
+}
+
+impl Default for HudLayout {
+    /// Como estaba antes de poder configurarlo (más el cronómetro, los avisos y la indicación).
+    fn default() -> Self {
+        let slot = |anchor, mx, my| Slot { anchor, margin: (mx, my), scale: 1.0, visible: true };
+        Self { slots: [
+            slot(Anchor::BottomLeft, 10, 10), // vida
+            slot(Anchor::BottomLeft, 10, 34), // inventario, arriba de la vida
+            slot(Anchor::TopLeft, 10, 10),    // minimapa
+            slot(Anchor::TopRight, 10, 10),   // cronómetro
+            slot(Anchor::Top, 0, 14),         // avisos
+            slot(Anchor::Center, 0, 90),      // indicación de lo que tienes enfrente
+        ] }
+    }
+}
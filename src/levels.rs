@@ -0,0 +1,190 @@
+use crate::constants::{MAP_H, MAP_W, TILE_DOOR, TILE_EMPTY, TILE_EXIT, TILE_GRASS};
+use crate::items::ItemKind;
+use crate::battle::PokeType;
+use crate::postfx::{Distort, Grade};
+use crate::enemies::EnemyKind;
+use crate::world::{self, gym_agua, gym_fuego, WorldMap};
+
+/// Qué hace falta para que el líder acepte el combate final (que es lo que gana el
+/// gimnasio: la meta sola no alcanza).
+#[derive(Copy, Clone, PartialEq)]
+pub enum Goal {
+    BeatLeader, // basta con ir a buscarlo
+    CollectAll, // solo pelea con todos los objetos recogidos
+}
+
+pub struct LevelDef {
+    pub name: &'static str,
+    pub goal: Goal,
+    pub element: PokeType, // tipo del gimnasio: salvajes y entrenadores
+    pub leader: &'static str,
+    pub badge: &'static str,
+    pub par: f64,          // segundos de referencia para el puntaje (ver score.rs)
+    pub difficulty: u8,    // 1..3, lo que muestra la selección de nivel
+    pub random: bool,      // el mapa sale de la semilla (la selección deja elegirla)
+    pub grade: Grade,      // color del nivel (post-proceso)
+    pub distort: Distort,
+}
+
+// Opciones del menú (el índice es el que recibe `build_level`)
+pub const LEVELS: &[LevelDef] = &[
+    LevelDef { name: "FUEGO EASY", goal: Goal::BeatLeader, element: PokeType::Fire,
+               leader: "BLAINE", badge: "MEDALLA VOLCAN", par: 60.0, difficulty: 1, random: false,
+               grade: Grade { lift: [10.0, 0.0, 0.0], gain: [1.08, 0.97, 0.88], sat: 1.1 },
+               distort: Distort::HeatHaze },
+    LevelDef { name: "LEVEL RANDOM", goal: Goal::CollectAll, element: PokeType::Water,
+               leader: "MISTY", badge: "MEDALLA CASCADA", par: 120.0, difficulty: 2, random: true,
+               grade: Grade { lift: [0.0, 4.0, 12.0], gain: [0.88, 1.0, 1.1], sat: 0.95 },
+               distort: Distort::Underwater },
+];
+
+// Archivo opcional que reemplaza el mapa generado de un nivel.
+pub fn level_file(idx: usize) -> String {
+    format!("assets/levels/level_{idx}.txt")
+}
+
+// NEW: construye el mapa según el índice del nivel.
+// Si existe `assets/levels/level_N.txt` se usa ese; si no, el generador
+// (más unos manchones de hierba alta para encuentros, lejos de `start`).
+pub fn build_level(idx: usize, seed: u64, start: (f64, f64)) -> WorldMap {
+    if let Some(m) = world::load_map_file(&level_file(idx)) {
+        return m;
+    }
+    let mut m = generate(idx, seed);
+    world::sprinkle_grass(&mut m, seed, start.0 as usize, start.1 as usize);
+    m
+}
+
+fn generate(idx: usize, seed: u64) -> WorldMap {
+    match idx {
+        // FUEGO
+        0 => gym_fuego(),
+
+        // AGUA (random seed)
+        1 => gym_agua(seed),
+
+        // PLANTA (placeholder: reusa fuego hasta que tengas su mapa)
+        2 => gym_fuego(),
+
+        _ => gym_fuego(),
+    }
+}
+
+// Celdas seguras alcanzables (sin lava) a más de `min_dist` del inicio.
+fn spawn_cells(map: &WorldMap, start: (f64, f64), min_dist: usize) -> Vec<(usize, usize)> {
+    let (sx, sy) = (start.0 as usize, start.1 as usize);
+    let mut cells: Vec<(usize, usize)> = world::reachable(map, sx, sy).into_iter()
+        .filter(|&(x, y)| map[y][x] == TILE_EMPTY && (x.abs_diff(sx) + y.abs_diff(sy)) > min_dist)
+        .collect();
+    if cells.is_empty() {
+        // Inicio encerrado (mapa de archivo raro): cualquier piso libre
+        for (y, row) in map.iter().enumerate() {
+            for (x, &t) in row.iter().enumerate() { if t == TILE_EMPTY { cells.push((x, y)); } }
+        }
+    }
+    cells
+}
+
+/// Objetos a repartir en el nivel: (tipo, x, y) en coordenadas de mundo.
+/// La llave siempre cae en una celda alcanzable sin cruzar la puerta.
+/// (La medalla ya no está tirada: la entrega el líder del gimnasio.)
+pub fn item_spawns(idx: usize, map: &WorldMap, seed: u64, start: (f64, f64)) -> Vec<(ItemKind, f64, f64)> {
+    let loose: &[ItemKind] = match idx {
+        0 => &[ItemKind::Key, ItemKind::Potion, ItemKind::Potion, ItemKind::Pokeball],
+        _ => &[ItemKind::Key, ItemKind::Potion, ItemKind::Potion, ItemKind::Potion,
+               ItemKind::Pokeball, ItemKind::Pokeball],
+    };
+
+    let mut cells = spawn_cells(map, start, 3);
+    let mut s = seed.wrapping_add(idx as u64 + 1) | 1;
+    let mut out = Vec::new();
+    for &kind in loose {
+        if cells.is_empty() { break; }
+        let i = world::rand_range(&mut s, cells.len() as i32) as usize;
+        let (x, y) = cells.swap_remove(i);
+        out.push((kind, x as f64 + 0.5, y as f64 + 0.5));
+    }
+    out
+}
+
+// Celda de acceso a la meta: un vecino transitable sin lava (primero el de la
+// izquierda, que es por donde se llega en los gimnasios generados). La hierba
+// que siembra `sprinkle_grass` también sirve.
+fn exit_access(map: &WorldMap) -> Option<(usize, usize)> {
+    for (y, row) in map.iter().enumerate() {
+        for x in (0..MAP_W).filter(|&x| row[x] == TILE_EXIT) {
+            let near = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+            if let Some(&c) = near.iter().find(|&&(nx, ny)| {
+                nx < MAP_W && ny < MAP_H && matches!(map[ny][nx], TILE_EMPTY | TILE_GRASS)
+            }) {
+                return Some(c);
+            }
+        }
+    }
+    None
+}
+
+// La salida misma (para el líder si no tiene dónde pararse al lado).
+fn exit_cell(map: &WorldMap) -> Option<(usize, usize)> {
+    map.iter().enumerate().find_map(|(y, row)| row.iter().position(|&t| t == TILE_EXIT).map(|x| (x, y)))
+}
+
+// Dónde espera el líder: al lado de la meta, sobre ella si no hay lugar, o (mapa
+// de archivo sin meta) en la celda alcanzable más lejos del inicio. Siempre hay
+// uno: sin él el gimnasio no se podría ganar.
+fn leader_cell(map: &WorldMap, start: (f64, f64)) -> Option<(usize, usize)> {
+    exit_access(map).or_else(|| exit_cell(map)).or_else(|| {
+        let (sx, sy) = (start.0 as usize, start.1 as usize);
+        spawn_cells(map, start, 0).into_iter().max_by_key(|&(x, y)| x.abs_diff(sx) + y.abs_diff(sy))
+    })
+}
+
+/// Puntos de control para los parciales: sobre el recorrido del inicio a la meta,
+/// pasando por la llave si hay puertas (de ahí en más, con las puertas abiertas),
+/// a 1/4, 1/2 y 3/4 del camino. En caminos cortos salen menos (nunca dos encimados).
+pub fn checkpoints(map: &WorldMap, start: (f64, f64), key: Option<(f64, f64)>) -> Vec<(f64, f64)> {
+    let cell = |p: (f64, f64)| (p.0 as usize, p.1 as usize);
+    let Some(exit) = exit_cell(map) else { return Vec::new(); };
+    let mut open = *map;
+    for t in open.iter_mut().flatten() { if *t == TILE_DOOR { *t = TILE_EMPTY; } }
+
+    let has_door = map.iter().flatten().any(|&t| t == TILE_DOOR);
+    let mut path = Vec::new();
+    let mut from = cell(start);
+    if let Some(k) = key.filter(|_| has_door)
+        && let Some(p) = world::find_path(map, from, cell(k))
+    {
+        path = p;
+        from = cell(k);
+    }
+    let Some(rest) = world::find_path(&open, from, exit) else { return Vec::new(); };
+    path.extend(rest);
+    if path.is_empty() { return Vec::new(); }
+
+    let mut out: Vec<(usize, usize)> = Vec::new();
+    for (x, y) in (1..4).map(|i| path[path.len() * i / 4]) {
+        let prev = out.last().copied().unwrap_or(cell(start));
+        if x.abs_diff(prev.0) + y.abs_diff(prev.1) >= 2 && (x, y) != exit { out.push((x, y)); }
+    }
+    out.into_iter().map(|(x, y)| (x as f64 + 0.5, y as f64 + 0.5)).collect()
+}
+
+/// Posiciones iniciales de los enemigos (lejos del jugador para no arrancar peleando).
+/// Los primeros son salvajes, luego un entrenador, y el líder espera junto a la meta.
+pub fn enemy_spawns(idx: usize, map: &WorldMap, seed: u64, start: (f64, f64)) -> Vec<(EnemyKind, f64, f64)> {
+    let count = if idx == 0 { 3 } else { 4 };
+    let mut cells = spawn_cells(map, start, 7);
+    let mut s = seed.wrapping_mul(31).wrapping_add(idx as u64 + 7) | 1;
+    let mut out = Vec::new();
+    for _ in 0..count {
+        if cells.is_empty() { break; }
+        let i = world::rand_range(&mut s, cells.len() as i32) as usize;
+        let (x, y) = cells.swap_remove(i);
+        out.push((EnemyKind::Wild, x as f64 + 0.5, y as f64 + 0.5));
+    }
+    if let Some(last) = out.last_mut() { last.0 = EnemyKind::Trainer; }
+    if let Some((x, y)) = leader_cell(map, start) {
+        out.push((EnemyKind::Leader, x as f64 + 0.5, y as f64 + 0.5));
+    }
+    out
+}
@@ -0,0 +1,297 @@
+use minifb::Key;
+
+use crate::battle::PokeType;
+use crate::constants::{rgb, HEIGHT, MAP_H, MAP_W, TILE_DOOR, TILE_EMPTY, TILE_EXIT, TILE_GRASS, TILE_HAZARD, WIDTH};
+use crate::ghost::Ghost;
+use crate::hud::{draw_badge_icon, draw_lock_icon, draw_text, draw_text_centered, put_pixel, text_width};
+use crate::input::{Action, Input};
+use crate::levels::{build_level, Goal, LEVELS};
+use crate::player::Player;
+use crate::postfx::{self, Pass};
+use crate::profile::Profile;
+use crate::title::draw_liquid;
+use crate::world::WorldMap;
+
+// Tarjetas de la izquierda y vista previa de la derecha
+const LIST_X: (usize, usize) = (30, 440);
+const LIST_Y: usize = 100;
+const CARD_MAX_H: usize = 104;
+const CARD_GAP: usize = 10;
+const PREVIEW_X: (usize, usize) = (460, 770);
+const CELL: usize = 11; // píxeles por casilla en la miniatura
+const THUMB_CELL: usize = 3; // y en la de cada tarjeta
+const SEED_DIGITS: usize = 19; // siempre entra en un u64
+
+/// Qué hacer después de una tecla en la selección de nivel.
+pub enum SelectResult { None, Back, Start(usize, u64) }
+
+/// Selección de nivel: una tarjeta por nivel (dificultad, mejor tiempo,
+/// medalla, candado, miniatura) y la vista previa del mapa que se va a jugar,
+/// armada con la misma semilla con la que arranca. En los niveles aleatorios la
+/// semilla se puede escribir (para repetir un mapa o correr contra su fantasma).
+pub struct LevelSelect {
+    pub selected: usize,
+    seeds: Vec<u64>,
+    typing: bool, // ya se escribió algo en la semilla (si no, el primer dígito la reemplaza)
+    maps: Vec<Option<(u64, WorldMap)>>, // mapa armado de cada nivel y con qué semilla
+    ghost: Option<(usize, u64, Option<f64>)>, // nivel, semilla y tiempo del fantasma
+    last_mouse: Option<(f32, f32)>,
+}
+
+impl LevelSelect {
+    pub fn new(seed: u64) -> Self {
+        let seeds = (0..LEVELS.len() as u64).map(|i| seed.wrapping_add(i.wrapping_mul(0x9E37_79B9_7F4A_7C15))).collect();
+        Self { selected: 0, seeds, typing: false, maps: vec![None; LEVELS.len()], ghost: None, last_mouse: None }
+    }
+
+    /// Se cambió un archivo de nivel (hot-reload): se vuelven a armar los mapas.
+    pub fn refresh(&mut self) { self.maps.fill(None); }
+
+    /// Después de una partida: el fantasma puede haber cambiado.
+    pub fn select(&mut self, level: usize) {
+        self.selected = level.min(LEVELS.len() - 1);
+        self.typing = false;
+        self.ghost = None;
+    }
+
+    // Mapa del nivel `i` con su semilla actual (se arma solo si cambió).
+    fn map(&mut self, i: usize) -> &WorldMap {
+        let seed = self.seeds[i];
+        if self.maps[i].as_ref().is_none_or(|m| m.0 != seed) {
+            let p = Player::new(); // donde arranca el nivel
+            self.maps[i] = Some((seed, build_level(i, seed, (p.x, p.y))));
+        }
+        &self.maps[i].as_ref().expect("recién armado").1
+    }
+
+    /// Una vez por frame mientras está abierta. `fresh` da una semilla nueva (Tab).
+    pub fn input(&mut self, input: &Input, profile: &Profile, fresh: fn() -> u64) -> SelectResult {
+        let n = LEVELS.len();
+        if input.pressed(Action::Pause) { return SelectResult::Back; }
+        if input.pressed(Action::Up) { self.select((self.selected + n - 1) % n); }
+        if input.pressed(Action::Down) { self.select((self.selected + 1) % n); }
+
+        // Mouse: pasar por encima elige, click juega (o pide otra semilla)
+        let mouse = input.mouse_pos().map(|(x, y)| (x as usize, y as usize));
+        let moved = input.mouse_pos() != self.last_mouse;
+        self.last_mouse = input.mouse_pos();
+        let card = mouse.and_then(|(x, y)| (0..n).find(|&i| inside(card_rect(i), x, y)));
+        if let Some(i) = card && i != self.selected && (moved || input.clicked()) { self.select(i); }
+        let reroll_clicked = input.clicked() && mouse.is_some_and(|(x, y)| inside(reroll_rect(), x, y));
+
+        // Semilla: dígitos, borrar y Tab (solo en niveles aleatorios)
+        if LEVELS[self.selected].random {
+            let seed = &mut self.seeds[self.selected];
+            let mut text = seed.to_string();
+            let mut edited = false;
+            for &key in input.keys_pressed() {
+                match key {
+                    Key::Tab => { *seed = fresh(); self.typing = false; }
+                    Key::Backspace => { text.pop(); edited = true; }
+                    k => if let Some(d) = digit(k) {
+                        if !self.typing && !edited { text.clear(); } // el primer dígito reemplaza la semilla
+                        if text.len() < SEED_DIGITS { text.push(d); }
+                        edited = true;
+                    },
+                }
+            }
+            if reroll_clicked { *seed = fresh(); self.typing = false; }
+            else if edited { *seed = text.parse().unwrap_or(0); self.typing = !text.is_empty(); }
+        }
+
+        let start = input.pressed(Action::Confirm) || (input.clicked() && card == Some(self.selected));
+        if start && profile.unlocked(self.selected) {
+            return SelectResult::Start(self.selected, self.seeds[self.selected]);
+        }
+        SelectResult::None
+    }
+
+    pub fn draw(&mut self, buf: &mut [u32], profile: &Profile, time: f64) {
+        let sel = self.selected;
+        let def = &LEVELS[sel];
+
+        // Fondo animado: lava o agua según el gimnasio elegido
+        draw_liquid(buf, time, def.element == PokeType::Water);
+        let radius = HEIGHT as f32 / ((WIDTH * WIDTH + HEIGHT * HEIGHT) as f32).sqrt();
+        postfx::apply(buf, Pass::Vignette { color: rgb(8, 6, 6), inner: 0.0, slope: 0.55, max: 0.55, radius });
+
+        // Título con sombra
+        draw_text_centered(buf, "SELECCIONA NIVEL", 32, 4, rgb(0, 0, 0));
+        draw_text_centered(buf, "SELECCIONA NIVEL", 30, 4, rgb(255, 255, 255));
+
+        for i in 0..LEVELS.len() { draw_card(buf, i, i == sel, profile, self.map(i), time); }
+        self.draw_preview(buf, profile, time);
+
+        let hint = if def.random { "↑/↓ ELEGIR  •  ENTER JUGAR  •  0-9/TAB SEMILLA  •  ESC VOLVER" }
+                   else { "↑/↓ ELEGIR  •  ENTER JUGAR  •  ESC VOLVER" };
+        draw_text_centered(buf, hint, HEIGHT - 38, 2, rgb(0, 0, 0));
+        draw_text_centered(buf, hint, HEIGHT - 40, 2, rgb(245, 245, 245));
+    }
+
+    // Miniatura grande del mapa con la semilla elegida, más líder, objetivo, semilla y fantasma.
+    fn draw_preview(&mut self, buf: &mut [u32], profile: &Profile, time: f64) {
+        let sel = self.selected;
+        let seed = self.seeds[sel];
+        if self.ghost.is_none_or(|g| (g.0, g.1) != (sel, seed)) {
+            self.ghost = Some((sel, seed, Ghost::load(sel, seed).map(|g| g.time)));
+        }
+        let ghost = self.ghost.and_then(|g| g.2);
+        let locked = !profile.unlocked(sel);
+        let def = &LEVELS[sel];
+
+        let (x0, x1) = PREVIEW_X;
+        let (w, h) = (MAP_W * CELL, MAP_H * CELL);
+        let y1 = LIST_Y + h + 150;
+        postfx::apply(buf, Pass::Rect { x0, y0: LIST_Y, x1, y1, color: rgb(0, 0, 0), alpha: 0.5 });
+        let (mx, my) = (x0 + (x1 - x0 - w) / 2, LIST_Y + 14);
+        draw_map(buf, self.map(sel), (mx, my), CELL, locked, time);
+        if locked {
+            let s = 96;
+            draw_lock_icon(buf, mx + (w - s) / 2, my + (h - s) / 2, s);
+        }
+
+        // Debajo: líder, objetivo, semilla y fantasma
+        let (tx, mut ty) = (x0 as i32 + 14, (my + h + 12) as i32);
+        let white = rgb(235, 235, 235);
+        let goal = match def.goal { Goal::BeatLeader => "VENCER AL LÍDER", Goal::CollectAll => "JUNTAR TODO Y VENCER AL LÍDER" };
+        draw_text(buf, &format!("LIDER: {}", def.leader), tx, ty, 2, white);
+        ty += 20;
+        draw_text(buf, &format!("OBJETIVO: {goal}"), tx, ty, 1, white);
+        ty += 18;
+        if def.random {
+            let (bx0, by0, bx1, by1) = seed_rect();
+            frame(buf, (bx0, by0, bx1, by1), rgb(255, 230, 120));
+            draw_text(buf, "SEMILLA", tx, ty + 8, 1, white);
+            let mut text = seed.to_string();
+            if self.typing && time % 1.0 < 0.5 { text.push('_'); }
+            draw_text(buf, &text, bx0 as i32 + 6, by0 as i32 + 9, 1, rgb(255, 230, 120));
+            let r = reroll_rect();
+            frame(buf, r, white);
+            draw_text(buf, "OTRA", r.0 as i32 + (r.2 - r.0 - text_width("OTRA", 2)) as i32 / 2, r.1 as i32 + 6, 2, white);
+        } else {
+            draw_text(buf, "MAPA FIJO", tx, ty + 8, 2, rgb(170, 170, 170));
+        }
+        ty += 44;
+        let ghost = match ghost {
+            Some(t) => format!("FANTASMA: {t:.2}s"),
+            None => "SIN FANTASMA PARA ESTA SEMILLA".to_string(),
+        };
+        draw_text(buf, &ghost, tx, ty, 1, rgb(170, 200, 255));
+    }
+}
+
+// Mapa casilla por casilla (la lava se mueve, la meta y el inicio titilan).
+// Con casillas grandes queda una línea oscura entre ellas.
+fn draw_map(buf: &mut [u32], map: &WorldMap, (mx, my): (usize, usize), cell: usize, locked: bool, time: f64) {
+    let pulse = 0.5 + 0.5 * (time * 4.0).sin();
+    let start = Player::new();
+    let (sx, sy) = (start.x as usize, start.y as usize);
+    let fill = if cell > 4 { cell - 1 } else { cell };
+    for (y, row) in map.iter().enumerate() {
+        for (x, &tile) in row.iter().enumerate() {
+            let mut c = match tile {
+                TILE_EMPTY => rgb(28, 28, 36),
+                TILE_GRASS => rgb(50, 120, 50),
+                TILE_DOOR => rgb(150, 100, 50),
+                TILE_EXIT => rgb(255, (150.0 + 80.0 * pulse) as u8, 80),
+                TILE_HAZARD => {
+                    let w = 0.5 + 0.5 * (time * 3.0 + (x + y) as f64 * 0.7).sin();
+                    rgb(200 + (50.0 * w) as u8, 50 + (70.0 * w) as u8, 20)
+                }
+                _ => rgb(90, 90, 110),
+            };
+            if (x, y) == (sx, sy) && pulse > 0.3 { c = rgb(255, 255, 255); }
+            if locked { c = (c >> 2) & 0x3F3F3F; }
+            for py in 0..fill {
+                for px in 0..fill { put_pixel(buf, mx + x * cell + px, my + y * cell + py, c); }
+            }
+        }
+    }
+}
+
+// Tarjeta de un nivel: nombre, dificultad, mejor tiempo/puntaje, miniatura del
+// mapa y medalla o candado.
+fn draw_card(buf: &mut [u32], i: usize, selected: bool, profile: &Profile, map: &WorldMap, time: f64) {
+    let def = &LEVELS[i];
+    let (x0, y0, x1, y1) = card_rect(i);
+    let locked = !profile.unlocked(i);
+    postfx::apply(buf, Pass::Rect { x0, y0, x1, y1, color: rgb(0, 0, 0), alpha: if selected { 0.6 } else { 0.4 } });
+    if selected {
+        let glow = (200.0 + 55.0 * (time * 3.0).sin()) as u8;
+        frame(buf, (x0, y0, x1, y1), rgb(255, glow, 100));
+    }
+
+    let (name_col, text_col) = match (locked, selected) {
+        (true, _) => (rgb(120, 120, 120), rgb(110, 110, 110)),
+        (false, true) => (rgb(255, 230, 120), rgb(235, 235, 235)),
+        (false, false) => (rgb(235, 235, 235), rgb(190, 190, 190)),
+    };
+    let (tx, ty) = (x0 as i32 + 14, y0 as i32 + 12);
+    draw_text(buf, def.name, tx + 2, ty + 2, 3, rgb(0, 0, 0));
+    draw_text(buf, def.name, tx, ty, 3, name_col);
+
+    // Dificultad: tres cuadraditos, llenos hasta el nivel
+    draw_text(buf, "DIFICULTAD", tx, ty + 36, 1, text_col);
+    for d in 0..3u8 {
+        let c = if d < def.difficulty { rgb(255, 120, 60) } else { rgb(70, 70, 70) };
+        let bx = x0 + 90 + d as usize * 12;
+        for yy in 0..8 { for xx in 0..8 { put_pixel(buf, bx + xx, ty as usize + 36 + yy, c); } }
+    }
+
+    let best = match (profile.best_time(i), profile.best_score(i)) {
+        (Some(t), Some(s)) => format!("MEJOR {t:.2}s  •  {s} PTS"),
+        (Some(t), None) => format!("MEJOR {t:.2}s"),
+        _ => "SIN COMPLETAR".to_string(),
+    };
+    draw_text(buf, &best, tx, ty + 52, 1, text_col);
+
+    let status = if locked { "GANA EL GIMNASIO ANTERIOR" } else if profile.has_badge(i) { def.badge } else { "SIN MEDALLA" };
+    draw_text(buf, status, tx, ty + 68, 1, text_col);
+
+    // Ícono a la derecha: medalla ganada o candado; y antes, la miniatura
+    let s = 40;
+    let (ix, iy) = (x1 - s - 14, y0 + (y1 - y0 - s) / 2);
+    if locked { draw_lock_icon(buf, ix, iy, s); } else if profile.has_badge(i) { draw_badge_icon(buf, ix, iy, s); }
+    let (tw, th) = (MAP_W * THUMB_CELL, MAP_H * THUMB_CELL);
+    if th + 8 <= y1 - y0 {
+        draw_map(buf, map, (ix - tw - 12, y0 + (y1 - y0 - th) / 2), THUMB_CELL, locked, time);
+    }
+}
+
+fn card_rect(i: usize) -> (usize, usize, usize, usize) {
+    let n = LEVELS.len();
+    let h = ((HEIGHT - LIST_Y - 60) / n - CARD_GAP).min(CARD_MAX_H);
+    let y0 = LIST_Y + i * (h + CARD_GAP);
+    (LIST_X.0, y0, LIST_X.1, y0 + h)
+}
+
+// Caja de la semilla y botón de otra semilla (debajo de la miniatura).
+fn seed_rect() -> (usize, usize, usize, usize) {
+    let y = LIST_Y + 14 + MAP_H * CELL + 12 + 38;
+    (PREVIEW_X.0 + 70, y, PREVIEW_X.1 - 84, y + 26)
+}
+
+fn reroll_rect() -> (usize, usize, usize, usize) {
+    let (_, y0, x1, y1) = seed_rect();
+    (x1 + 8, y0, PREVIEW_X.1 - 14, y1)
+}
+
+fn inside((x0, y0, x1, y1): (usize, usize, usize, usize), x: usize, y: usize) -> bool {
+    x >= x0 && x < x1 && y >= y0 && y < y1
+}
+
+// Borde de 1 px.
+fn frame(buf: &mut [u32], (x0, y0, x1, y1): (usize, usize, usize, usize), c: u32) {
+    for x in x0..x1 { put_pixel(buf, x, y0, c); put_pixel(buf, x, y1 - 1, c); }
+    for y in y0..y1 { put_pixel(buf, x0, y, c); put_pixel(buf, x1 - 1, y, c); }
+}
+
+fn digit(k: Key) -> Option<char> {
+    use Key::*;
+    let d = match k {
+        Key0 | NumPad0 => 0, Key1 | NumPad1 => 1, Key2 | NumPad2 => 2, Key3 | NumPad3 => 3, Key4 | NumPad4 => 4,
+        Key5 | NumPad5 => 5, Key6 | NumPad6 => 6, Key7 | NumPad7 => 7, Key8 | NumPad8 => 8, Key9 | NumPad9 => 9,
+        _ => return None,
+    };
+    char::from_digit(d, 10)
+}
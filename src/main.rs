@@ -4,30 +4,77 @@ mod player;
 mod textures;
 mod render;
 mod hud;
+mod font;
 mod audio;
 mod sprites;
-
-use minifb::{Key, Window, WindowOptions, MouseMode};
+mod hotreload;
+mod items;
+mod levels;
+mod enemies;
+mod battle;
+mod profile;
+mod projectiles;
+mod save;
+mod settings;
+mod pause;
+mod input;
+mod sim;
+mod replay;
+mod ghost;
+mod debug;
+mod camfx;
+mod postfx;
+mod score;
+mod transition;
+mod title;
+mod levelselect;
+mod minimap;
+mod layout;
+mod messages;
+
+use minifb::{Window, WindowOptions};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use constants::{WIDTH, HEIGHT, MAP_W, MAP_H, TILE_EXIT, TILE_HAZARD, PLAYER_MAX_HP};
+use constants::{WIDTH, HEIGHT, TICK, MAX_TICKS_PER_FRAME, PLAYER_MAX_HP};
 use player::Player;
-use world::gym_fuego;
 use textures::TextureSet;
 use audio::Audio;
-use sprites::SpriteManager;
-
-use crate::world::gym_agua;
+use hotreload::AssetWatcher;
+use levels::{LEVELS, level_file};
+use profile::Profile;
+use pause::{PauseMenu, PauseResult};
+use input::{Action, Input, PadEvent};
+use sim::{Sim, SimEvent};
+use replay::{Ending, Playback, Replay, REPLAY_PATH, SPEEDS};
+use ghost::Ghost;
+use debug::DebugOverlay;
+use camfx::CameraFx;
+use render::Lens;
+use postfx::{Pass, PostFx};
+use score::Score;
+use transition::{Kind, Transition};
+use title::TitleScreen;
+use levelselect::{LevelSelect, SelectResult};
+use minimap::Minimap;
+use layout::{HudLayout, Widget};
+use messages::MessageLog;
+
+// Al morir: cuánto dura la caída (segundos reales) y a qué velocidad va el mundo mientras
+const DEATH_TIME: f64 = 1.2;
+const DEATH_SLOWMO: f64 = 0.3;
 
 #[derive(Copy, Clone, PartialEq)]
-enum GameState { Menu, Playing, Win, Dead }
+enum GameState { Title, Menu, Playing, Paused, Battle, Win, Dead }
 
-// NEW: opciones de nivel (puedes renombrar y luego mapearlos a distintos mapas)
-const LEVELS: &[&str] = &["FUEGO EASY", "LEVEL RANDOM"];
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let arg_after = |flag: &str| args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned();
 
-const MOUSE_SENS: f64 = 0.004;
+    // Verificador sin ventana: `--verify-replay archivo` sale con 0 si la repetición gana el nivel
+    if let Some(path) = arg_after("--verify-replay") {
+        std::process::exit(verify_replay(&path));
+    }
 
-fn main() {
     let mut window = Window::new(
         "Gimnasio Fuego - FPS: 0 | Enter para iniciar",
         WIDTH, HEIGHT,
@@ -37,30 +84,66 @@ fn main() {
     window.limit_update_rate(Some(Duration::from_micros(16_667)));
 
     let mut buffer = vec![0u32; WIDTH * HEIGHT];
+    let mut last_frame = buffer.clone(); // el frame anterior sin las líneas de CRT (de ahí salen transiciones y derrota)
     let mut zbuffer = vec![0.0f64; WIDTH];
-    let mut state = GameState::Menu;
+    let mut state = GameState::Title;
 
-    // NEW: world_map ahora es mutable porque lo cambiaremos según el nivel
-    let mut world_map = gym_fuego();
-    let mut p = Player::new();
+    // Nivel en curso (mapa, jugador, objetos, enemigos, combate); ver sim.rs
+    let mut sim = Sim::new(0, new_seed(), Player::new());
     let mut textures = TextureSet::load();
-
+    font::set(font::Font::load()); // assets/font.bdf o .png si hay; si no la 5x7
+    let mut title = TitleScreen::new(); // logo, fondo animado y demo
+    let mut attract = false; // mostrando una demo desde el título (cualquier tecla vuelve)
 
     // Audio (con toggle mute)
     let mut audio = Audio::new();
-    let mut muted = false;
-    let mut prev_m_down = false;
-
-    // Timers varios
-    let mut step_timer: f64 = 0.0;
-    let mut last_mouse_x: Option<f32> = None;
-
-    // Sprites
-    let mut sprites = SpriteManager::new_fire_gym();
+    // Perfil persistente (medallas, tiempos, opciones, equipo)
+    let mut profile = Profile::load();
+    let mut muted = profile.settings.muted;
+    audio.set_muted(muted);
+    audio.set_volumes(profile.settings.music_volume, profile.settings.sfx_volume);
+    let mut pause = PauseMenu::new();
+    // Acciones (teclado + gamepad) con las teclas de input.cfg
+    let mut input = Input::new();
+
+    // Simulación a paso fijo: tiempo pendiente y pose del tick anterior (para interpolar)
+    let mut sim_acc: f64 = 0.0;
+    let mut prev_pose = sim.player.pose();
+
+    // Repeticiones: la partida en curso se graba; `--replay archivo` reproduce una
+    let mut recording: Option<Replay> = None;
+    let mut playback: Option<Playback> = None;
+    // Fantasma: la mejor corrida de este nivel con esta semilla (si hay)
+    let mut ghost: Option<Ghost> = None;
+    let mut ghost_sprite = ghost::sprite();
+    if let Some(path) = arg_after("--replay") {
+        match Replay::load(&path) {
+            Some(r) => {
+                sim = r.sim();
+                sim.player.set_fov(profile.settings.fov_deg);
+                textures = level_textures(r.level);
+                ghost = Ghost::load(r.level, r.seed);
+                playback = Some(Playback::new(r));
+                state = GameState::Playing;
+            }
+            None => eprintln!("{path}: no es una repetición válida"),
+        }
+    }
 
     // Vida/daño
     let mut damage_flash: f64 = 0.0; // 0..0.5s para overlay
-    let mut hazard_tick: f64 = 0.0;  // ticks de lava
+    let mut camfx = CameraFx::new(); // temblor, bamboleo, FOV al correr, roll
+    let mut post = PostFx::new(); // viñeta, color por nivel, distorsión, CRT (assets/postfx.cfg)
+    let mut death_frame: Vec<u32> = Vec::new(); // último frame antes de morir (se apaga a gris)
+    let mut death_t: f64 = 0.0;
+    let mut dying: Option<f64> = None; // cámara lenta entre el golpe final y la pantalla de derrota
+    let mut trans = Transition::new(); // fundido / iris / disolución entre pantallas
+    let mut shown = state; // la pantalla que está en `buffer` (para saber cuándo empezar una transición)
+    let mut log = MessageLog::new(); // avisos en pantalla (el último también va al título)
+
+    // Pantalla de victoria: medalla recién ganada, puntaje y si fue récord
+    let mut new_badge: Option<&'static str> = None;
+    let mut win_score: Option<(Score, bool)> = None;
 
     // Overlay de lava (animación)
     let mut lava_phase: f64 = 0.0;
@@ -70,214 +153,483 @@ fn main() {
     let mut fps_timer = Instant::now();
     let mut frames = 0u32;
     let mut fps = 0u32;
+    let mut debug = DebugOverlay::new(); // F3
+    let mut minimap = Minimap::new(); // Tab automapa, N gira, +/- zoom
+    let mut hud_layout = HudLayout::load(); // dónde va cada pieza del HUD (assets/hud.cfg)
+
+    // Selección de nivel (tarjetas, vista previa y semilla)
+    let mut select = LevelSelect::new(new_seed());
 
-    // NEW: estado del menú (selección + debounce)
-    let mut selected_level: usize = 0;
-    let mut prev_up = false;
-    let mut prev_down = false;
-    let mut current_level: usize = 0; // para título dinámico y assets por nivel
+    // Modo dev (`--dev` o POKE_DEV=1): recarga assets y niveles en caliente
+    let dev_mode = args.iter().any(|a| a == "--dev") || std::env::var_os("POKE_DEV").is_some();
+    let mut watcher = if dev_mode {
+        let mut w = AssetWatcher::new(0.5);
+        w.watch_dir("assets");
+        for i in 0..LEVELS.len() { w.watch_file(&level_file(i)); }
+        Some(w)
+    } else { None };
 
 
     while window.is_open() {
         let now = Instant::now();
         let dt = (now - last).as_secs_f64();
         last = now;
+        debug.begin(dt);
 
-         if window.is_key_down(Key::Escape) {
-        break; // salir del juego
-    }
-       if window.is_key_down(Key::Enter) {
-    current_level = selected_level;
-    world_map = build_level(current_level); // genera el mapa según nivel
-    p = Player::new();
-
-    // Sprites por nivel (por ahora usamos los de fuego como default)
-    sprites = SpriteManager::new_fire_gym();
-
-    // (Opcional) texturas por nivel:
-    // Si luego implementas TextureSet::load_water(), descomenta:
-    if current_level == 1 {
-        // textures = TextureSet::load_water();
-    } else {
-        textures = TextureSet::load();
-    }
+        // Teclado + gamepad → acciones (y aviso si el control se enchufa/desenchufa)
+        match input.update(&window, dt) {
+            Some(PadEvent::Connected(name)) => log.push(format!("Control conectado: {name}"), 2.5),
+            Some(PadEvent::Disconnected) => log.push("Control desconectado".to_string(), 2.5),
+            None => {}
+        }
+        // Durante una transición (o cayendo muerto) no se atiende nada
+        if trans.active() || dying.is_some() { input.block(); }
+
+        // Pausa (Escape): en el título y los carteles finales sale del juego
+        // (en el menú vuelve al título y jugando pausa, ver abajo)
+        if input.pressed(Action::Pause) && playback.is_none()
+            && matches!(state, GameState::Title | GameState::Win | GameState::Dead)
+        {
+            break;
+        }
 
-    state = GameState::Playing;
-}
+        // Reproducción: Enter pausa, ←/→ velocidad, C cámara libre, Esc vuelve al menú
+        if let Some(pb) = playback.as_mut().filter(|_| !attract) {
+            let turn = input.take_tick(profile.settings.mouse_sens).turn as f64;
+            if !pb.controls(&input, sim.player.pose(), turn, dt) {
+                playback = None;
+                state = GameState::Menu;
+            }
+        }
+
+        // F9: cargar quicksave (desde el menú o jugando)
+        if input.pressed(Action::QuickLoad) && playback.is_none()
+            && matches!(state, GameState::Menu | GameState::Playing)
+            && let Some(qs) = save::read_quicksave()
+            && qs.level < LEVELS.len()
+        {
+            select.select(qs.level);
+            textures = level_textures(qs.level);
+            trans.start(Kind::Fade, &last_frame);
+            sim = Sim::restore(qs, new_seed());
+            sim.player.set_fov(profile.settings.fov_deg);
+            recording = None; // la repetición (y el fantasma) se graban desde el inicio del nivel
+            ghost = None;
+            log.push("Partida cargada".to_string(), 2.0);
+            state = GameState::Playing;
+        }
 
-        // Mouse horizontal
-        if let Some((mx, _)) = window.get_mouse_pos(MouseMode::Pass) {
-            if let Some(prev_x) = last_mouse_x {
-                p.rotate((mx - prev_x) as f64 * MOUSE_SENS);
+        // Hot-reload (solo modo dev): la posición del jugador no se toca
+        if let Some(w) = watcher.as_mut() {
+            let changed = w.poll(dt);
+            if changed.iter().any(|c| textures.uses(c)) { textures.reload(); }
+            if changed.iter().any(|c| font::Font::uses(c)) { font::set(font::Font::load()); }
+            if changed.iter().any(|c| TitleScreen::uses(c)) { title.reload(); }
+            if changed.iter().any(|c| c.starts_with("assets/levels")) { select.refresh(); }
+            if changed.iter().any(|c| postfx::PostConfig::uses(c)) { post.cfg = postfx::PostConfig::load(); }
+            if changed.iter().any(|c| HudLayout::uses(c)) { hud_layout = HudLayout::load(); }
+            let lvl = level_file(sim.level);
+            for c in &changed {
+                audio.reload(c);
+                sim.sprites.reload(c);
+                sim.enemies.reload(c);
+                sim.projectiles.reload(c);
+                if c.as_path() == std::path::Path::new(&lvl)
+                    && let Some(m) = world::load_map_file(&lvl)
+                {
+                    sim.map = m;
+                }
             }
-            last_mouse_x = Some(mx);
         }
 
-        // Ticks jugador/efectos
-        p.tick(dt);
-        if damage_flash > 0.0 { damage_flash -= dt; }
-        lava_phase += dt;
+        // Efectos visuales (van con el frame, no con la simulación; al morir, en cámara lenta)
+        let vdt = if dying.is_some() { dt * DEATH_SLOWMO } else { dt };
+        if damage_flash > 0.0 { damage_flash -= vdt; }
+        log.update(dt);
+        lava_phase += vdt;
 
-        // Toggle MUTE (tecla M, con debounce)
-        let m_down = window.is_key_down(Key::M);
-        if m_down && !prev_m_down {
+        // Toggle MUTE (tecla M)
+        if input.pressed(Action::Mute) && state != GameState::Paused {
             muted = audio.toggle_muted();
+            profile.settings.muted = muted;
+            profile.save();
         }
-        prev_m_down = m_down;
 
-        match state {
-            GameState::Menu => {
-                // NEW: dibuja el menú con niveles
-                draw_menu_levels(&mut buffer, selected_level, LEVELS);
-
-                // Navegación ↑ / ↓ con debounce
-                let up = window.is_key_down(Key::Up);
-                let down = window.is_key_down(Key::Down);
-
-                if up && !prev_up {
-                    selected_level = if selected_level == 0 {
-                        LEVELS.len() - 1
-                    } else {
-                        selected_level - 1
-                    };
-                }
-                if down && !prev_down {
-                    selected_level = (selected_level + 1) % LEVELS.len();
-                }
-                prev_up = up;
-                prev_down = down;
-
-                // Enter: cargar nivel seleccionado
-                // Enter: cargar nivel seleccionado
-if window.is_key_down(Key::Enter) {
-    current_level = selected_level;
-
-    // Mapa por nivel (AGUA ya crea un layout random en build_level)
-    world_map = build_level(current_level);
-
-    // Reset jugador/sprites
-    p = Player::new();
-    sprites = SpriteManager::new_fire_gym(); // cámbialo si tienes sprites por nivel
-
-    // Texturas por nivel (azules para AGUA)
-    textures = if current_level == 1 {
-        // Requiere textures::TextureSet::load_water()
-        TextureSet::load_water()
-    } else {
-        TextureSet::load()
-    };
+        // F3: overlay de rendimiento
+        if input.pressed(Action::Debug) { debug.visible = !debug.visible; }
 
-    state = GameState::Playing;
-}
+        // F5: quicksave (posición, vida, mapa, objetos y enemigos)
+        if input.pressed(Action::QuickSave) && state == GameState::Playing && playback.is_none() {
+            let msg = match save::write_quicksave(&sim.snapshot()) {
+                Ok(()) => "Partida guardada (F9 para cargar)".to_string(),
+                Err(e) => format!("No se pudo guardar: {e}"),
+            };
+            log.push(msg, 2.0);
+        }
 
+        // ===== Simulación a paso fijo =====
+        // Exploración y combate avanzan de a TICK segundos sin importar los FPS
+        // (mismo daño de lava, mismas colisiones, repetible). Lo que sobra del
+        // frame queda en `sim_acc` y el render interpola la cámara con eso.
+        debug.skip();
+        let running = matches!(state, GameState::Playing | GameState::Battle) && !trans.active();
+        match playback.as_ref() {
+            _ if !running => sim_acc = 0.0,
+            _ if dying.is_some() => sim_acc = (sim_acc + dt * DEATH_SLOWMO).min(TICK * MAX_TICKS_PER_FRAME as f64),
+            Some(pb) if pb.paused => {}
+            pb => {
+                let speed = pb.map_or(1.0, |pb| SPEEDS[pb.speed]);
+                sim_acc = (sim_acc + dt * speed).min(TICK * MAX_TICKS_PER_FRAME as f64 * speed);
             }
-            GameState::Playing => {
-                let move_speed = 4.0 * dt;
-                let rot_speed  = 2.8 * dt;
-
-                // Movimiento + SFX pasos
-                let mut moving = false;
-                if window.is_key_down(Key::W) { p.try_move(move_speed,  p.dir_x,  p.dir_y, &world_map); moving = true; }
-                if window.is_key_down(Key::S) { p.try_move(-move_speed, p.dir_x,  p.dir_y, &world_map); moving = true; }
-                let px = -p.dir_y; let py = p.dir_x;
-                if window.is_key_down(Key::A) { p.try_move(move_speed,  px,  py, &world_map); moving = true; }
-                if window.is_key_down(Key::D) { p.try_move(move_speed, -px, -py, &world_map); moving = true; }
-
-                if window.is_key_down(Key::Left)  { p.rotate(-rot_speed); }
-                if window.is_key_down(Key::Right) { p.rotate( rot_speed); }
-
-                if moving {
-                    step_timer += dt;
-                    if step_timer > 0.38 { audio.play_step(); step_timer = 0.0; }
-                } else { step_timer = 0.0; }
-
-                // Daño por hazard (lava)
-                let tx = p.x as usize; let ty = p.y as usize;
-                if world_map[ty][tx] == TILE_HAZARD {
-                    hazard_tick += dt;
-                    if hazard_tick >= 0.5 {
-                        p.damage(12);
-                        damage_flash = 0.5;
-                        hazard_tick = 0.0;
+        }
+        if (!running || dying.is_some()) && playback.is_none() {
+            input.take_tick(0.0); // lo apretado en menús no llega a la simulación
+        }
+        // Cayendo muerto: el mundo sigue en cámara lenta, sin entrada del jugador
+        while sim_acc >= TICK && dying.is_some() {
+            sim_acc -= TICK;
+            prev_pose = sim.player.pose();
+            sim.step_world();
+        }
+        while sim_acc >= TICK && matches!(state, GameState::Playing | GameState::Battle) && dying.is_none() {
+            // Entrada del tick: la de la repetición, o la del jugador (y se graba)
+            let ti = match playback.as_mut() {
+                Some(pb) => match pb.next() {
+                    Some(t) => t,
+                    None => {
+                        pb.paused = true;
+                        log.push("Fin de la repetición (Esc para salir)".to_string(), 5.0);
+                        break;
+                    }
+                },
+                None => input.take_tick(profile.settings.mouse_sens),
+            };
+            if let Some(r) = recording.as_mut() { r.push(ti, &sim); }
+            sim_acc -= TICK;
+            prev_pose = sim.player.pose();
+
+            for ev in sim.step(&ti) {
+                match ev {
+                    SimEvent::Pickup => audio.play_pickup(),
+                    SimEvent::Step => { audio.play_step(); camfx.step(); }
+                    SimEvent::Hurt => { damage_flash = 0.5; camfx.add_trauma(0.6); }
+                    SimEvent::Notice(msg) => log.push(msg, 2.5),
+                    SimEvent::BattleStart => state = GameState::Battle,
+                    SimEvent::BattleEnd => state = GameState::Playing,
+                    SimEvent::Split(i) => {
+                        let t = sim.splits[i];
+                        let vs = match ghost.as_ref().and_then(|g| g.splits.get(i)) {
+                            Some(best) => format!(" ({:+.2}s)", t - best),
+                            None => String::new(),
+                        };
+                        log.push(format!("Parcial {}: {t:.2}s{vs}", i + 1), 3.0);
+                    }
+                    SimEvent::Won => {
+                        // Vencer al líder cierra el gimnasio con su medalla
+                        new_badge = Some(LEVELS[sim.level].badge);
+                        let sc = score::score(sim.level, sim.time, &sim.stats);
+                        let mut record = false;
+                        if playback.is_none() {
+                            record = profile.best_time(sim.level).is_none_or(|b| sim.time < b)
+                                || profile.best_score(sim.level).is_none_or(|b| sc.total > b);
+                            let p = &sim.player;
+                            profile.complete(sim.level, sim.time, sc.total, &p.party, &p.inventory);
+                        }
+                        win_score = Some((sc, record));
+                        // Récord para esta semilla: pasa a ser el fantasma
+                        if let Some(r) = recording.as_mut()
+                            && ghost.as_ref().is_none_or(|g| sim.time < g.time)
+                        {
+                            r.ghost.time = sim.time;
+                            r.ghost.splits = sim.splits.clone();
+                            r.ghost.save(sim.level, sim.seed);
+                        }
+                        save_recording(&mut recording);
+                        // audio.play_win();
+                        state = GameState::Win;
+                    }
+                    SimEvent::Died if state == GameState::Battle => {
+                        // Perder un combate: no hay cámara que caiga, el combate se apaga a gris
+                        save_recording(&mut recording);
+                        death_frame.clone_from(&last_frame);
+                        death_t = 0.0;
+                        state = GameState::Dead;
+                    }
+                    SimEvent::Died => {
+                        // Primero la caída en cámara lenta; la pantalla de derrota viene después
+                        save_recording(&mut recording);
+                        prev_pose = sim.player.pose();
+                        sim_acc = 0.0;
+                        dying = Some(0.0);
                     }
-                } else {
-                    hazard_tick = 0.0;
                 }
+            }
+        }
+        debug.lap("sim");
+
+        if let Some(t) = dying.as_mut() {
+            *t += dt;
+            camfx.collapse(*t / DEATH_TIME);
+            if *t >= DEATH_TIME {
+                dying = None;
+                camfx.collapse(0.0);
+                death_frame.clone_from(&last_frame);
+                death_t = 0.0;
+                state = GameState::Dead;
+            }
+        }
 
-                // Muerte
-                if p.hp <= 0 {
-                    state = GameState::Dead;
-                }
+        // Demo del título: termina con cualquier tecla, al acabarse la repetición o al ganar/perder
+        if attract {
+            let over = playback.as_ref().is_none_or(|pb| pb.paused || pb.pos as f64 * TICK >= title::DEMO_MAX);
+            if any_key(&input) || over || matches!(state, GameState::Win | GameState::Dead) {
+                attract = false;
+                playback = None;
+                dying = None;
+                camfx.collapse(0.0);
+                title.enter(true);
+                state = GameState::Title;
+            }
+        }
 
-                // Sprites
-                sprites.update(dt);
+        // Cambió la pantalla en la simulación: la transición sale del frame anterior
+        if state != shown {
+            if let Some(kind) = transition_kind(shown, state) { trans.start(kind, &last_frame); }
+            shown = state;
+        }
 
-                // Render 3D
-                render::clear_bg(&mut buffer, &textures.sky, p.dir_x, p.dir_y);
-                render::floorcast(&mut buffer, &world_map,
-                  p.x, p.y, p.dir_x, p.dir_y, p.plane_x, p.plane_y,
-                  lava_phase);
-                render::raycast(&mut buffer, &mut zbuffer, &world_map, &textures,
-                                p.x, p.y, p.dir_x, p.dir_y, p.plane_x, p.plane_y,
-                                8.0, 0.15);
+        match state {
+            GameState::Title => {
+                title.draw(&mut buffer);
+                if any_key(&input) {
+                    state = GameState::Menu;
+                } else if let Some(r) = title.update(dt) {
+                    // Nadie toca nada: demo con una repetición
+                    sim = r.sim();
+                    sim.player.set_fov(profile.settings.fov_deg);
+                    textures = level_textures(r.level);
+                    ghost = None;
+                    playback = Some(Playback::new(r));
+                    attract = true;
+                    state = GameState::Playing;
+                }
+            }
+            GameState::Menu => {
+                select.draw(&mut buffer, &profile, lava_phase);
+                match select.input(&input, &profile, new_seed) {
+                    SelectResult::None => {}
+                    // Escape: vuelve al título
+                    SelectResult::Back => {
+                        title.enter(false);
+                        state = GameState::Title;
+                    }
+                    // Con la semilla de la vista previa: el mapa es el que se ve
+                    SelectResult::Start(level, seed) => {
+                        (sim, textures, recording) = start_level(level, seed, &profile);
+                        log.clear();
+                        ghost = Ghost::load(sim.level, sim.seed);
+                        state = GameState::Playing;
+                    }
+                }
+            }
+            GameState::Playing => {
+                // Cámara entre el tick anterior y el actual (o la cámara libre de la repetición)
+                // (con efectos de cámara encima; la cámara libre va sin ellos)
+                camfx.update(vdt, &prev_pose, &sim.player.pose());
+                let (cam, lens) = match playback.as_ref().and_then(|pb| pb.free_cam) {
+                    Some(c) => (c, Lens::default()),
+                    None => camfx.apply(prev_pose.lerp(&sim.player.pose(), sim_acc / TICK)),
+                };
 
+                // Render 3D
+                render::clear_bg(&mut buffer, &textures.sky, cam.dir_x, cam.dir_y, &lens);
+                debug.lap("clear_bg");
+                render::floorcast(&mut buffer, &sim.map,
+                  cam.x, cam.y, cam.dir_x, cam.dir_y, cam.plane_x, cam.plane_y,
+                  lava_phase, profile.settings.column_step(), &lens);
+                debug.lap("floorcast");
+                render::raycast(&mut buffer, &mut zbuffer, &sim.map, &textures,
+                                cam.x, cam.y, cam.dir_x, cam.dir_y, cam.plane_x, cam.plane_y,
+                                8.0, 0.15, profile.settings.column_step(), &lens);
+                debug.lap("raycast");
+
+                let mut visible: Vec<_> = sim.sprites.visible().chain(sim.enemies.sprites()).chain(sim.projectiles.sprites()).collect();
+                let ghost_at = ghost.as_ref().and_then(|g| g.at(sim.time));
+                if let Some((x, y, facing)) = ghost_at {
+                    (ghost_sprite.x, ghost_sprite.y, ghost_sprite.facing) = (x, y, facing);
+                    ghost_sprite.update(vdt);
+                    visible.push(&ghost_sprite);
+                }
                 render::draw_sprites(&mut buffer, &zbuffer,
-                                     p.x, p.y, p.dir_x, p.dir_y, p.plane_x, p.plane_y,
-                                     &sprites, 0.20);
+                                     cam.x, cam.y, cam.dir_x, cam.dir_y, cam.plane_x, cam.plane_y,
+                                     &visible, 0.20, &lens);
+                debug.lap("draw_sprites");
+                post.world(&mut buffer, &LEVELS[sim.level], lava_phase);
+                debug.lap("postfx");
 
                 // HUD
-                hud::draw_minimap(&mut buffer, &world_map, p.x, p.y, p.dir_x, p.dir_y);
-                hud::draw_health_bar(&mut buffer, p.hp, PLAYER_MAX_HP);
-                render::draw_damage_overlay(&mut buffer, (damage_flash / 0.5) as f32);
-
-                // Win
-                if world_map[ty][tx] == TILE_EXIT {
-                    // audio.play_win();
-                    state = GameState::Win;
+                // (cada pieza donde diga el layout; el automapa tapa todo)
+                if minimap.update(&input, &mut profile.settings, dt) { profile.save(); }
+                let markers = minimap::markers(&sim, ghost_at.map(|(x, y, _)| (x, y)));
+                let screen = (WIDTH, buffer.len() / WIDTH);
+                if let Some(a) = hud_layout.place(Widget::Minimap, minimap::SIZE, screen) {
+                    minimap.draw(&mut buffer, &a, &sim.map, &cam, &markers, lava_phase);
+                }
+                if let Some(a) = hud_layout.place(Widget::Health, hud::HEALTH_SIZE, screen) {
+                    hud::draw_health_bar(&mut buffer, &a, sim.player.hp, PLAYER_MAX_HP);
+                }
+                let (inv, left) = (&sim.player.inventory, sim.remaining());
+                if let Some(a) = hud_layout.place(Widget::Inventory, hud::inventory_size(inv, left), screen) {
+                    hud::draw_inventory(&mut buffer, &a, inv, left);
+                }
+                if let Some(a) = hud_layout.place(Widget::Timer, hud::TIMER_SIZE, screen) {
+                    hud::draw_timer(&mut buffer, &a, sim.time, ghost.as_ref().map(|g| g.time));
+                }
+                if let Some(a) = hud_layout.place(Widget::Messages, log.size(), screen) { log.draw(&mut buffer, &a); }
+                if let Some(msg) = messages::prompt(&sim).filter(|_| dying.is_none() && !minimap.full)
+                    && let Some(a) = hud_layout.place(Widget::Prompt, hud::message_size(&msg), screen)
+                {
+                    hud::draw_prompt(&mut buffer, &a, &msg, lava_phase);
+                }
+                if minimap.full { minimap::draw_full(&mut buffer, &sim.map, &cam, &markers, &input.bindings, lava_phase); }
+                debug.lap("hud");
+                post.screen(&mut buffer, (damage_flash / 0.5) as f32);
+                if attract { title::draw_demo_banner(&mut buffer, lava_phase); }
+                if debug.visible { debug.draw(&mut buffer, fps, &sim); }
+
+                // Escape: pausa con este frame de fondo
+                if input.pressed(Action::Pause) && playback.is_none() {
+                    pause.open(&buffer);
+                    state = GameState::Paused;
+                }
+            }
+            GameState::Paused => {
+                match pause.input(&mut input, &mut profile.settings) {
+                    PauseResult::None => {}
+                    PauseResult::Resume => state = GameState::Playing,
+                    PauseResult::Restart => {
+                        // Misma semilla: mismo mapa, para correr contra el fantasma
+                        trans.start(Kind::Fade, &last_frame);
+                        (sim, textures, recording) = start_level(sim.level, sim.seed, &profile);
+                        log.clear();
+                        ghost = Ghost::load(sim.level, sim.seed);
+                        state = GameState::Playing;
+                    }
+                    PauseResult::QuitToMenu => {
+                        recording = None;
+                        state = GameState::Menu;
+                    }
+                    // Se aplica al vuelo; se guarda al salir de la pantalla de ajustes
+                    PauseResult::Changed => {
+                        let s = &profile.settings;
+                        audio.set_volumes(s.music_volume, s.sfx_volume);
+                        sim.player.set_fov(s.fov_deg);
+                    }
+                    PauseResult::SettingsClosed => profile.save(),
+                }
+                if state == GameState::Paused { pause.draw(&mut buffer, &profile.settings, &input.bindings); }
+            }
+            GameState::Battle => {
+                match sim.battle.as_ref() {
+                    Some(b) => b.draw(&mut buffer, &sim.player.party),
+                    None => state = GameState::Playing,
                 }
             }
             GameState::Win => {
-    draw_win(&mut buffer, &textures);
-    if window.is_key_down(Key::Space) || window.is_key_down(Key::M) {
-        p = Player::new();
+    if let Some((sc, record)) = &win_score { draw_win(&mut buffer, &textures, new_badge, sc, *record); }
+    if input.pressed(Action::Confirm) {
+        playback = None;
         state = GameState::Menu;
+        // Sugiere el siguiente gimnasio si se acaba de abrir
+        if sim.level + 1 < LEVELS.len() && profile.unlocked(sim.level + 1) {
+            select.select(sim.level + 1);
+        } else {
+            select.select(sim.level); // (el fantasma puede ser nuevo)
+        }
     }
 }
 
 GameState::Dead => {
-    draw_dead(&mut buffer, &textures);
-    if window.is_key_down(Key::Space) || window.is_key_down(Key::M) {
-        p = Player::new();
+    // El último frame se apaga a gris y después aparece el cartel
+    death_t += dt;
+    let k = death_t.min(1.0) as f32;
+    if death_frame.len() == buffer.len() { buffer.copy_from_slice(&death_frame); }
+    post.run(&mut buffer, &[Pass::Grayscale(k), Pass::Brightness(1.0 - 0.45 * k)]);
+    if k >= 1.0 { draw_dead(&mut buffer); }
+    if input.pressed(Action::Confirm) {
+        playback = None;
         state = GameState::Menu;
     }
 }
 
         }
 
+        // Cambió desde la pantalla misma (menú, pausa...): sale de lo recién dibujado
+        if state != shown {
+            if let Some(kind) = transition_kind(shown, state) { trans.start(kind, &buffer); }
+            shown = state;
+        }
+        trans.draw(&mut buffer, dt);
+
         // FPS + estado de mute en el título
         frames += 1;
         if fps_timer.elapsed() >= Duration::from_secs(1) { fps = frames; frames = 0; fps_timer = Instant::now(); }
-        let mute_tag = if muted { " [MUTE]" } else { "" };
-let title = match state {
+        let mute_tag = match (muted, dev_mode) {
+            (true, true) => " [MUTE] [DEV]",
+            (true, false) => " [MUTE]",
+            (false, true) => " [DEV]",
+            (false, false) => "",
+        };
+let name = LEVELS[sim.level].name;
+let title_bar = match state {
+    GameState::Title => format!("Poke Raycaster - FPS: {fps}{mute_tag} | Cualquier tecla para empezar • Esc salir"),
+    GameState::Playing | GameState::Battle if attract => format!("Poke Raycaster - FPS: {fps}{mute_tag} | Demo: cualquier tecla para volver"),
     GameState::Menu => {
         // Muestra el nivel seleccionado en el título
-        let sel = LEVELS[selected_level];
-        format!("Gimnasio {sel} - FPS: {fps}{mute_tag} | ↑/↓ elegir nivel • Enter jugar")
+        let sel = LEVELS[select.selected].name;
+        let best = match profile.best_time(select.selected) {
+            Some(t) => format!(" • Mejor: {t:.1}s"),
+            None => String::new(),
+        };
+        format!("Gimnasio {sel} - FPS: {fps}{mute_tag}{best} | ↑/↓ elegir nivel • Enter jugar • F9 cargar")
+    }
+    GameState::Playing | GameState::Battle if let Some(pb) = &playback => {
+        let t = pb.pos as f64 * TICK;
+        let status = if pb.paused { "PAUSA".to_string() } else { format!("x{}", SPEEDS[pb.speed]) };
+        let extra = match log.latest() {
+            Some(msg) => msg.to_string(),
+            None => "Enter pausa • ←/→ velocidad • C cámara libre • Esc salir".to_string(),
+        };
+        format!("Repetición {name} [{status}] {t:.1}/{:.1}s - FPS: {fps}{mute_tag} | {extra}", pb.replay.duration())
     }
     GameState::Playing => {
-        let name = LEVELS[current_level];
-        format!("Gimnasio {name} - FPS: {fps}{mute_tag} | Mouse rotación, W/A/S/D moverte")
+        // Cronómetro y récord de esta semilla
+        let record = match &ghost {
+            Some(g) => format!(" (récord {:.2}s)", g.time),
+            None => String::new(),
+        };
+        let timer = format!("{:.2}s{record}", sim.time);
+        match log.latest() {
+            Some(msg) => format!("Gimnasio {name} {timer} - FPS: {fps}{mute_tag} | {msg}"),
+            None => format!("Gimnasio {name} {timer} - FPS: {fps}{mute_tag} | Mouse rotación, W/A/S/D moverte, F pokébola, Q atacar"),
+        }
+    }
+    GameState::Paused => {
+        format!("Gimnasio {name} - FPS: {fps}{mute_tag} | Pausa: ↑/↓ elegir • ←/→ ajustar • Enter aceptar • Esc volver")
+    }
+    GameState::Battle => {
+        format!("Gimnasio {name} - FPS: {fps}{mute_tag} | ¡Combate! ↑/↓ elegir • Espacio confirmar • Retroceso volver")
     }
     GameState::Win => {
-        let name = LEVELS[current_level];
-        format!("Gimnasio {name} - FPS: {fps}{mute_tag} | ¡Ganaste! Enter para reiniciar")
+        format!("Gimnasio {name} - FPS: {fps}{mute_tag} | ¡Ganaste! Enter para volver al menú")
     }
     GameState::Dead => {
-        let name = LEVELS[current_level];
-        format!("Gimnasio {name} - FPS: {fps}{mute_tag} | ¡Derrotado! Enter para reintentar")
+        format!("Gimnasio {name} - FPS: {fps}{mute_tag} | ¡Derrotado! Enter para volver al menú")
     }
 };
-        window.set_title(&title);
+        window.set_title(&title_bar);
+        last_frame.copy_from_slice(&buffer);
+        post.finish(&mut buffer);
 
         if window.update_with_buffer(&mut buffer, WIDTH, HEIGHT).is_err() { break; }
     }
@@ -285,146 +637,82 @@ let title = match state {
 
 // ========= Helpers =========
 
-// NEW: construye el mapa según el índice del nivel.
-// Por ahora todos usan el mismo; cuando tengas más, cambia aquí.
-fn build_level(idx: usize) -> [[i32; MAP_W]; MAP_H] {
-    match idx {
-        // FUEGO
-        0 => gym_fuego(),
-
-        // AGUA (random seed)
-        1 => {
-            let seed = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_nanos() as u64;
-            gym_agua(seed)
-        }
-
-        // PLANTA (placeholder: reusa fuego hasta que tengas su mapa)
-        2 => gym_fuego(),
-
-        _ => gym_fuego(),
-    }
+// Semilla para niveles aleatorios (mapa + objetos).
+fn new_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64
 }
 
-#[inline]
-fn lerp_u8(a: u8, b: u8, t: f32) -> u8 {
-    let t = t.clamp(0.0, 1.0);
-    (a as f32 + (b as f32 - a as f32) * t).round().clamp(0.0, 255.0) as u8
+// Jugador nuevo con el equipo (curado) e inventario del perfil.
+fn new_player(profile: &Profile) -> Player {
+    let mut p = Player::new();
+    p.party = profile.party.clone();
+    p.party.heal_all();
+    p.inventory = profile.inventory.clone();
+    p.set_fov(profile.settings.fov_deg);
+    p
 }
 
-#[inline]
-fn blend_rgb(bg: u32, fg: u32, alpha: f32) -> u32 {
-    use constants::rgb;
-    let a = alpha.clamp(0.0, 1.0);
-    let inv = 1.0 - a;
-    let br = ((bg >> 16) & 0xFF) as f32;
-    let bgc= ((bg >>  8) & 0xFF) as f32;
-    let bb = ( bg        & 0xFF) as f32;
-    let fr = ((fg >> 16) & 0xFF) as f32;
-    let fgc= ((fg >>  8) & 0xFF) as f32;
-    let fb = ( fg        & 0xFF) as f32;
-    rgb((br*inv + fr*a) as u8, (bgc*inv + fgc*a) as u8, (bb*inv + fb*a) as u8)
+// Texturas por nivel (azules para AGUA)
+fn level_textures(idx: usize) -> TextureSet {
+    if idx == 1 { TextureSet::load_water() } else { TextureSet::load() }
 }
 
-fn draw_menu_levels(buf: &mut [u32], selected: usize, options: &[&str]) {
-    use constants::{rgb, WIDTH, HEIGHT};
-
-    // ------------------ Fondo: LAVA procedural ------------------
-    let w = WIDTH as i32; let h = HEIGHT as i32;
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            let fx = x as f32 / WIDTH as f32;
-            let fy = y as f32 / HEIGHT as f32;
-
-            // Ondas entrecruzadas (estático; si quieres animar, suma una fase global)
-            let s1 = (fx * 11.0 + (fy * 4.0).sin() * 0.7).sin();
-            let s2 = (fy * 9.0  + (fx * 5.0).cos() * 0.6).cos();
-            let s3 = ((fx * 3.0 + fy * 6.0).sin()) * 0.5;
-            let v = ((s1 + s2 + s3) * 0.28 + 0.55).clamp(0.0, 1.0);
-
-            let deep  = (110u8,  20u8,  10u8); // rojo oscuro
-            let mid   = (210u8,  60u8,  20u8); // naranja
-            let light = (255u8, 200u8,  60u8); // amarillo
-
-            // 2 pasos de paleta para más contraste
-            let (r, g, b) = if v < 0.6 {
-                let t = (v / 0.6).clamp(0.0, 1.0);
-                (lerp_u8(deep.0, mid.0, t), lerp_u8(deep.1, mid.1, t), lerp_u8(deep.2, mid.2, t))
-            } else {
-                let t = ((v - 0.6) / 0.4).clamp(0.0, 1.0);
-                (lerp_u8(mid.0, light.0, t), lerp_u8(mid.1, light.1, t), lerp_u8(mid.2, light.2, t))
-            };
-
-            // Vignette (oscurece bordes para que el texto destaque)
-            let cx = x as i32 - (w / 2);
-            let cy = y as i32 - (h / 2);
-            let d  = ((cx*cx + cy*cy) as f32).sqrt() / ((w.min(h)) as f32 / 2.0);
-            let dark = (d * 0.55).clamp(0.0, 0.55); // 0..0.55
-            let base = rgb(r, g, b);
-            let final_px = blend_rgb(base, rgb(8, 6, 6), dark);
+// Todo lo que cambia al entrar a un nivel: simulación nueva con la semilla,
+// texturas y la grabación de la repetición.
+fn start_level(idx: usize, seed: u64, profile: &Profile) -> (Sim, TextureSet, Option<Replay>) {
+    let p = new_player(profile);
+    let rec = Replay::new(idx, seed, &p);
+    (Sim::new(idx, seed, p), level_textures(idx), Some(rec))
+}
 
-            buf[y * WIDTH + x] = final_px;
-        }
+// Con qué transición se pasa de una pantalla a otra (pausar y volver es instantáneo;
+// a la derrota se llega por la cámara lenta y el frame que se apaga a gris).
+fn transition_kind(from: GameState, to: GameState) -> Option<Kind> {
+    use GameState::*;
+    match (from, to) {
+        (Menu, Playing) | (Battle, Playing) => Some(Kind::Iris),
+        (Playing, Battle) => Some(Kind::Dissolve),
+        (Playing, Win) | (Title, Playing) | (_, Menu) | (_, Title) => Some(Kind::Fade),
+        _ => None,
     }
+}
 
-    // ------------------ Panel oscuro para texto ------------------
-    let title_scale = (HEIGHT / 70).max(3);
-    let opt_scale   = (HEIGHT / 90).max(3);
-
-    let total = options.len() as i32;
-    let start_y = (HEIGHT as i32 / 2) - ((total * (8*opt_scale as i32)) / 2);
-    let pad_y = 10i32.max((6 * opt_scale as i32) / 5);
-    let panel_y0 = (start_y - pad_y).clamp(0, HEIGHT as i32 - 1) as usize;
-    let panel_y1 = (start_y + total * (8 * opt_scale as i32) + pad_y)
-        .clamp(0, HEIGHT as i32 - 1) as usize;
-    let panel_x0 = (WIDTH as i32 / 8) as usize;
-    let panel_x1 = (WIDTH as i32 * 7 / 8) as usize;
-
-    for y in panel_y0..panel_y1 {
-        let row = y * WIDTH;
-        for x in panel_x0..panel_x1 {
-            let bg = buf[row + x];
-            buf[row + x] = blend_rgb(bg, rgb(0, 0, 0), 0.40); // 40% negro encima
-        }
+// Guarda la repetición de la partida que acaba de terminar (pisa la anterior).
+fn save_recording(rec: &mut Option<Replay>) {
+    if let Some(r) = rec.take()
+        && let Err(e) = r.save(REPLAY_PATH)
+    {
+        eprintln!("No se pudo guardar {REPLAY_PATH}: {e}");
     }
+}
 
-    // ------------------ Título con sombra ------------------
-    let title_y = HEIGHT/6;
-    hud::draw_text_centered(buf, "SELECCIONA NIVEL", title_y + 2, title_scale, rgb(0,0,0)); // sombra
-    hud::draw_text_centered(buf, "SELECCIONA NIVEL", title_y,     title_scale, rgb(255,255,255));
-
-    // ------------------ Opciones (sombra + resaltado) ------------------
-    for (i, name) in options.iter().enumerate() {
-        let y = (start_y + i as i32 * (8 * opt_scale as i32)) as usize;
-        let (col, col_shadow) = if i == selected {
-            (rgb(255,230,120), rgb(20,12,0)) // texto dorado + sombra cálida
-        } else {
-            (rgb(235,235,235), rgb(0,0,0))   // blanco suave + sombra negra
-        };
-
-        // Sombra 1px hacia abajo
-        hud::draw_text_centered(buf, &format!("{}",
-            if i == selected { format!("> {} <", name) } else { name.to_string() }
-        ), y + 2, opt_scale, col_shadow);
-
-        // Texto principal
-        hud::draw_text_centered(buf, &format!("{}",
-            if i == selected { format!("> {} <", name) } else { name.to_string() }
-        ), y, opt_scale, col);
+// `--verify-replay`: corre la repetición sin ventana y dice si gana el nivel.
+fn verify_replay(path: &str) -> i32 {
+    let Some(r) = Replay::load(path) else {
+        eprintln!("{path}: no es una repetición válida");
+        return 2;
+    };
+    let (ending, ticks) = replay::run_headless(&r);
+    let secs = ticks as f64 * TICK;
+    let name = LEVELS[r.level].name;
+    match ending {
+        Ending::Won => { println!("OK: {name} ganado en {secs:.2}s ({ticks} ticks)"); 0 }
+        Ending::Died => { println!("FALLA: {name} perdido a los {secs:.2}s"); 1 }
+        Ending::Unfinished => { println!("FALLA: la repetición de {name} termina a los {secs:.2}s sin ganar"); 1 }
     }
+}
 
-    // ------------------ Pie de ayuda (sombra + texto) ------------------
-    let hint_scale = (HEIGHT / 110).max(2);
-    let hint_y = (HEIGHT*5)/6;
-    hud::draw_text_centered(buf, "↑/↓ ELEGIR  •  ENTER JUGAR  •  ESC SALIR", hint_y + 2, hint_scale, rgb(0,0,0));
-    hud::draw_text_centered(buf, "↑/↓ ELEGIR  •  ENTER JUGAR  •  ESC SALIR", hint_y,     hint_scale, rgb(245,245,245));
+// "Presiona cualquier tecla": teclado o el botón de aceptar del control.
+fn any_key(input: &Input) -> bool {
+    !input.keys_pressed().is_empty() || input.pressed(Action::Confirm)
 }
 
 // ======= Pantallas de victoria y derrota ya existentes =======
 
-fn draw_win(buf: &mut [u32], textures: &textures::TextureSet) {
+fn draw_win(buf: &mut [u32], textures: &textures::TextureSet, badge: Option<&str>, sc: &Score, record: bool) {
     use constants::{rgb, WIDTH, HEIGHT};
 
     // 🔥 Fondo: usa la textura wall_fire_a como “fuego”
@@ -440,13 +728,7 @@ fn draw_win(buf: &mut [u32], textures: &textures::TextureSet) {
     }
 
     // 🔦 Oscurecer un poco para resaltar texto
-    for px in buf.iter_mut() {
-        let (r, g, b) = ((*px >> 16) & 0xFF, (*px >> 8) & 0xFF, *px & 0xFF);
-        let r = (r as f32 * 0.6) as u32;
-        let g = (g as f32 * 0.6) as u32;
-        let b = (b as f32 * 0.6) as u32;
-        *px = (r << 16) | (g << 8) | b;
-    }
+    postfx::apply(buf, Pass::Brightness(0.6));
 
     // 🧱 Marco blanco
     for x in 0..WIDTH {
@@ -458,47 +740,67 @@ fn draw_win(buf: &mut [u32], textures: &textures::TextureSet) {
         buf[y * WIDTH + (WIDTH - 1)] = rgb(255, 255, 255);
     }
 
-    // 📝 Texto central
-    let scale = (HEIGHT / 80).max(3);
-    let y_main = HEIGHT / 2 - (7 * scale) / 2;
-    hud::draw_text_centered(buf, "¡GANASTE!", y_main, scale + 1, rgb(255, 230, 120));
-
-    // 🧭 Subtexto
-    let y_sub = y_main + (7 * scale) + (6 * scale / 5);
-    hud::draw_text_centered(
-        buf,
-        "Presiona espacio o m para volver al menú",
-        y_sub,
-        scale.saturating_sub(1).max(2),
-        rgb(255, 255, 255),
-    );
-}
+    // 📝 Título
+    let gold = rgb(255, 230, 120);
+    hud::draw_text_centered(buf, "¡GANASTE!", 40, 8, gold);
+    if let Some(name) = badge {
+        hud::draw_text_centered(buf, &format!("OBTUVISTE LA {name}"), 112, 3, rgb(255, 200, 90));
+    }
 
+    // 🧮 Desglose: concepto, cuánto y puntos (alineados a la derecha)
+    let (x_label, x_right, scale) = (200, 600, 3);
+    let right = |buf: &mut [u32], text: &str, y: i32, color: u32| {
+        let w = hud::text_width(text, scale) as i32;
+        hud::draw_text(buf, text, x_right - w, y, scale, color);
+    };
+    let mut y = 160;
+    for (label, detail, pts) in &sc.lines {
+        hud::draw_text(buf, label, x_label, y, scale, rgb(255, 255, 255));
+        hud::draw_text(buf, detail, x_label + 160, y, scale, rgb(200, 200, 200));
+        let color = if *pts < 0 { rgb(255, 120, 100) } else { rgb(140, 255, 140) };
+        right(buf, &format!("{pts:+}"), y, color);
+        y += 28;
+    }
+    for x in x_label..x_right { hud::put_pixel(buf, x as usize, y as usize - 6, rgb(255, 255, 255)); }
+    hud::draw_text(buf, "TOTAL", x_label, y, scale, gold);
+    right(buf, &sc.total.to_string(), y, gold);
+
+    // ⭐ Rango con estrellas (y la medalla al lado si la hubo)
+    let y_rank = y as usize + 44;
+    hud::draw_text(buf, &sc.rank.to_string(), x_label, y_rank as i32, 8, gold);
+    for i in 0..3 {
+        let c = if i < sc.stars { rgb(255, 215, 60) } else { rgb(70, 60, 50) };
+        draw_star(buf, x_label as usize + 80 + i * 64, y_rank, 56, c);
+    }
+    if badge.is_some() { hud::draw_badge_icon(buf, x_right as usize - 56, y_rank, 56); }
 
-fn draw_dead(buf: &mut [u32], textures: &textures::TextureSet) {
-    use constants::{rgb, WIDTH, HEIGHT};
+    // 🏆 Récord (tiempo o puntaje)
+    if record {
+        hud::draw_text_centered(buf, "NUEVO RECORD", y_rank + 72, 4, rgb(120, 255, 200));
+    }
 
-    // 🔥 Fondo: usa la textura wall_fire_a como “fuego”
-    let fire = &textures.wall_fire_a;
+    // 🧭 Subtexto
+    hud::draw_text_centered(buf, "Presiona enter o espacio para volver al menú", HEIGHT - 40, 2, rgb(255, 255, 255));
+}
 
-    for y in 0..HEIGHT {
-        for x in 0..WIDTH {
-            let tx = (x * fire.w / WIDTH) as usize;
-            let ty = (y * fire.h / HEIGHT) as usize;
-            let color = fire.data[ty * fire.w + tx];
-            buf[y * WIDTH + x] = color;
+// Estrella de 5 puntas rellena (radio alterna entre punta y valle según el ángulo).
+fn draw_star(buf: &mut [u32], x0: usize, y0: usize, size: usize, color: u32) {
+    let r = size as f64 / 2.0;
+    for y in 0..size {
+        for x in 0..size {
+            let (dx, dy) = (x as f64 + 0.5 - r, y as f64 + 0.5 - r);
+            let a = dy.atan2(dx) + std::f64::consts::FRAC_PI_2;
+            let t = (a * 5.0 / std::f64::consts::TAU).rem_euclid(1.0);
+            let edge = r * (0.45 + 0.55 * ((t - 0.5).abs() * 2.0).powi(2));
+            if dx * dx + dy * dy <= edge * edge { hud::put_pixel(buf, x0 + x, y0 + y, color); }
         }
     }
+}
 
-    // 🔦 Oscurecer un poco para resaltar texto
-    for px in buf.iter_mut() {
-        let (r, g, b) = ((*px >> 16) & 0xFF, (*px >> 8) & 0xFF, *px & 0xFF);
-        let r = (r as f32 * 0.6) as u32;
-        let g = (g as f32 * 0.6) as u32;
-        let b = (b as f32 * 0.6) as u32;
-        *px = (r << 16) | (g << 8) | b;
-    }
+fn draw_dead(buf: &mut [u32]) {
+    use constants::{rgb, WIDTH, HEIGHT};
 
+    // (el fondo es el último frame ya en grises, ver GameState::Dead)
     // 🧱 Marco blanco
     for x in 0..WIDTH {
         buf[x] = rgb(255, 255, 255);
@@ -515,12 +817,5 @@ fn draw_dead(buf: &mut [u32], textures: &textures::TextureSet) {
     hud::draw_text_centered(buf, "¡PERDISTE!", y_main, scale + 1, rgb(255, 230, 120));
 
     // 🧭 Subtexto
-    let y_sub = y_main + (7 * scale) + (6 * scale / 5);
-    hud::draw_text_centered(
-        buf,
-        "Presiona espacio o m para volver al menú",
-        y_sub,
-        scale.saturating_sub(1).max(2),
-        rgb(255, 255, 255),
-    );
+    hud::draw_text_centered(buf, "Presiona enter o espacio para volver al menú", HEIGHT - 40, 2, rgb(255, 255, 255));
 }
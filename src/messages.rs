@@ -0,0 +1,80 @@
+use crate::constants::{TILE_DOOR, TILE_EXIT, TILE_HAZARD};
+use crate::hud;
+use crate::items::ItemKind;
+use crate::layout::Area;
+use crate::sim::Sim;
+
+const MAX_LINES: usize = 4;
+const FADE: f64 = 0.6;  // los últimos segundos de cada aviso se va apagando
+const LINE: usize = 28; // alto de cada renglón (aviso + separación), en unidades base
+
+struct Message {
+    text: String,
+    left: f64,
+}
+
+/// Avisos en pantalla: cada uno dura unos segundos y se apaga solo. Los más nuevos
+/// van abajo; si llega uno que ya está, solo se renueva (empujar una puerta cerrada
+/// lo manda cada tick).
+pub struct MessageLog {
+    list: Vec<Message>,
+}
+
+impl MessageLog {
+    pub fn new() -> Self { Self { list: Vec::new() } }
+
+    pub fn push(&mut self, text: String, secs: f64) {
+        if let Some(i) = self.list.iter().position(|m| m.text == text) {
+            let m = self.list.remove(i);
+            self.list.push(Message { left: m.left.max(secs), ..m });
+            return;
+        }
+        if self.list.len() == MAX_LINES { self.list.remove(0); }
+        self.list.push(Message { text, left: secs });
+    }
+
+    pub fn update(&mut self, dt: f64) {
+        for m in &mut self.list { m.left -= dt; }
+        self.list.retain(|m| m.left > 0.0);
+    }
+
+    pub fn clear(&mut self) { self.list.clear(); }
+
+    /// El último aviso (para el título de la ventana).
+    pub fn latest(&self) -> Option<&str> { self.list.last().map(|m| m.text.as_str()) }
+
+    /// Tamaño base de la lista: el aviso más ancho por la cantidad de renglones.
+    pub fn size(&self) -> (usize, usize) {
+        let w = self.list.iter().map(|m| hud::message_size(&m.text).0).max().unwrap_or(1);
+        (w, (self.list.len() * LINE).max(1))
+    }
+
+    /// Un aviso por renglón, centrados dentro de `a`.
+    pub fn draw(&self, buf: &mut [u32], a: &Area) {
+        for (i, m) in self.list.iter().enumerate() {
+            let (w, h) = hud::message_size(&m.text);
+            let (w, h) = (a.px(w as f32), a.px(h as f32));
+            let line = Area { x: a.x + (a.w - w) / 2, y: a.y + a.px((i * LINE) as f32), w, h, scale: a.scale };
+            hud::draw_message(buf, &line, &m.text, (m.left / FADE).min(1.0) as f32);
+        }
+    }
+}
+
+/// Qué decirle al jugador sobre lo que tiene enfrente o bajo los pies (`None` si nada).
+pub fn prompt(sim: &Sim) -> Option<String> {
+    let p = &sim.player;
+    let (tx, ty) = (p.x as usize, p.y as usize);
+    match sim.map[ty][tx] {
+        TILE_HAZARD => return Some("¡Sal de la lava!".to_string()),
+        TILE_EXIT => return Some(sim.exit_blocked()),
+        _ => {}
+    }
+    // Casilla de enfrente (un poco más lejos que donde se empuja la puerta)
+    let (fx, fy) = ((p.x + p.dir_x * 1.1) as usize, (p.y + p.dir_y * 1.1) as usize);
+    match sim.map.get(fy).and_then(|r| r.get(fx)) {
+        Some(&TILE_DOOR) if p.inventory.count(ItemKind::Key) > 0 => Some("Avanza para abrir (-1 llave)".to_string()),
+        Some(&TILE_DOOR) => Some("Puerta cerrada: necesitas una llave".to_string()),
+        Some(&TILE_EXIT) => Some(sim.exit_blocked()),
+        _ => None,
+    }
+}
@@ -0,0 +1,261 @@
+use crate::constants::{alpha_blend, rgb, HEIGHT, MAP_H, MAP_W, TILE_DOOR, TILE_EMPTY, TILE_EXIT, TILE_GRASS, TILE_HAZARD, WIDTH};
+use crate::enemies::EnemyKind;
+use crate::hud::{draw_badge_icon, draw_text, draw_text_centered, put_pixel};
+use crate::input::{key_name, Action, Bindings, Input};
+use crate::layout::Area;
+use crate::items::ItemKind;
+use crate::player::Pose;
+use crate::postfx::{self, Pass};
+use crate::settings::{Settings, MAP_ZOOM_RANGE};
+use crate::sim::Sim;
+use crate::world::WorldMap;
+
+const RADIUS: usize = 70; // radio del minimapa a escala 1
+const ZOOM_STEP: f64 = 1.25;
+
+/// Tamaño base para el layout del HUD.
+pub const SIZE: (usize, usize) = (2 * RADIUS + 1, 2 * RADIUS + 1);
+
+/// Qué se marca encima del mapa.
+#[derive(Copy, Clone, PartialEq)]
+pub enum Icon { Enemy(EnemyKind), Item(ItemKind), Ghost, Exit }
+
+pub struct Marker { pub x: f64, pub y: f64, pub icon: Icon }
+
+/// Enemigos, objetos, metas (y el fantasma si hay) del nivel en curso.
+pub fn markers(sim: &Sim, ghost: Option<(f64, f64)>) -> Vec<Marker> {
+    let mut out: Vec<Marker> = sim.enemies.list.iter()
+        .map(|e| Marker { x: e.sprite.x, y: e.sprite.y, icon: Icon::Enemy(e.kind) })
+        .chain(sim.sprites.items.iter().map(|i| Marker { x: i.sprite.x, y: i.sprite.y, icon: Icon::Item(i.kind) }))
+        .collect();
+    for (y, row) in sim.map.iter().enumerate() {
+        for (x, &t) in row.iter().enumerate() {
+            if t == TILE_EXIT { out.push(Marker { x: x as f64 + 0.5, y: y as f64 + 0.5, icon: Icon::Exit }); }
+        }
+    }
+    if let Some((x, y)) = ghost { out.push(Marker { x, y, icon: Icon::Ghost }); }
+    out
+}
+
+/// Minimapa redondo alrededor del jugador (girando con él o con el norte
+/// arriba, con zoom) y el automapa de pantalla completa con leyenda (Tab).
+/// El giro y el zoom se guardan en las opciones del perfil.
+pub struct Minimap {
+    pub full: bool,
+    zoom: f64, // el que se ve; va hacia el de las opciones suavemente
+    rotate: bool,
+}
+
+impl Minimap {
+    pub fn new() -> Self { Self { full: false, zoom: 0.0, rotate: true } }
+
+    /// Teclas del mapa y zoom suave. Devuelve true si cambió una opción (hay que guardar el perfil).
+    pub fn update(&mut self, input: &Input, s: &mut Settings, dt: f64) -> bool {
+        if input.pressed(Action::Automap) { self.full = !self.full; }
+        let zoom = if input.pressed(Action::MapZoomIn) { s.map_zoom * ZOOM_STEP }
+                   else if input.pressed(Action::MapZoomOut) { s.map_zoom / ZOOM_STEP }
+                   else { s.map_zoom };
+        let zoom = zoom.clamp(MAP_ZOOM_RANGE.0, MAP_ZOOM_RANGE.1);
+        let rotate = s.map_rotate != input.pressed(Action::MapRotate);
+        let changed = zoom != s.map_zoom || rotate != s.map_rotate;
+        (s.map_zoom, s.map_rotate, self.rotate) = (zoom, rotate, rotate);
+        if self.zoom == 0.0 { self.zoom = zoom; }
+        self.zoom += (zoom - self.zoom) * (1.0 - (-12.0 * dt).exp());
+        changed
+    }
+
+    /// El redondo en el lugar que le da el layout del HUD (el zoom va con su escala).
+    pub fn draw(&self, buf: &mut [u32], a: &Area, map: &WorldMap, pose: &Pose, markers: &[Marker], time: f64) {
+        let radius = (a.w - 1) / 2;
+        let (cx, cy) = (a.x + radius, a.y + radius);
+        let view = View::new(pose, self.zoom * a.scale as f64, self.rotate);
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let d2 = dx * dx + dy * dy;
+                if d2 > radius * radius { continue; }
+                if cx + dx < 0 || cy + dy < 0 || cx + dx >= WIDTH as i32 || cy + dy >= HEIGHT as i32 { continue; }
+                let (x, y) = ((cx + dx) as usize, (cy + dy) as usize);
+                let c = if d2 > (radius - 2) * (radius - 2) {
+                    rgb(220, 210, 190) // aro
+                } else {
+                    let (wx, wy) = view.to_world(dx as f64, dy as f64);
+                    alpha_blend(buf[y * WIDTH + x], cell_color(map, wx, wy, time), 210)
+                };
+                put_pixel(buf, x, y, c);
+            }
+        }
+
+        // Íconos dentro del círculo
+        for m in markers {
+            let (sx, sy) = view.to_screen(m.x, m.y);
+            if sx * sx + sy * sy > ((radius - 6) * (radius - 6)) as f64 { continue; }
+            draw_icon(buf, m.icon, cx + sx as i32, cy + sy as i32, a.px(3.0).max(2), time);
+        }
+
+        // Jugador: flecha en el centro (hacia arriba si el mapa gira)
+        let (fx, fy) = if self.rotate { (0.0, -1.0) } else { normalized(pose.dir_x, pose.dir_y) };
+        draw_arrow(buf, cx as f64, cy as f64, fx, fy, 6.0 * a.scale as f64, rgb(255, 255, 255));
+
+        // Norte en el aro cuando gira
+        if self.rotate {
+            let (nx, ny) = view.to_screen(pose.x, pose.y - 1.0);
+            let (nx, ny) = normalized(nx, ny);
+            let r = (radius - 9) as f64;
+            draw_text(buf, "N", cx + (nx * r) as i32 - 2, cy + (ny * r) as i32 - 3, 1, rgb(255, 80, 60));
+        }
+    }
+}
+
+// Pasa de píxeles del minimapa (relativos al centro) a mundo y al revés.
+struct View { px: f64, py: f64, fwd: (f64, f64), right: (f64, f64), zoom: f64, rotate: bool }
+
+impl View {
+    fn new(p: &Pose, zoom: f64, rotate: bool) -> Self {
+        Self { px: p.x, py: p.y, fwd: normalized(p.dir_x, p.dir_y), right: normalized(p.plane_x, p.plane_y), zoom, rotate }
+    }
+
+    fn to_world(&self, sx: f64, sy: f64) -> (f64, f64) {
+        let (sx, sy) = (sx / self.zoom, sy / self.zoom);
+        if !self.rotate { return (self.px + sx, self.py + sy); }
+        (self.px + sx * self.right.0 - sy * self.fwd.0, self.py + sx * self.right.1 - sy * self.fwd.1)
+    }
+
+    fn to_screen(&self, wx: f64, wy: f64) -> (f64, f64) {
+        let (dx, dy) = (wx - self.px, wy - self.py);
+        if !self.rotate { return (dx * self.zoom, dy * self.zoom); }
+        ((dx * self.right.0 + dy * self.right.1) * self.zoom, -(dx * self.fwd.0 + dy * self.fwd.1) * self.zoom)
+    }
+}
+
+fn normalized(x: f64, y: f64) -> (f64, f64) {
+    let l = x.hypot(y).max(1e-9);
+    (x / l, y / l)
+}
+
+// Color de la casilla en el punto de mundo (afuera del mapa: casi negro).
+fn cell_color(map: &WorldMap, wx: f64, wy: f64, time: f64) -> u32 {
+    if wx < 0.0 || wy < 0.0 || wx >= MAP_W as f64 || wy >= MAP_H as f64 { return rgb(10, 10, 14); }
+    tile_color(map[wy as usize][wx as usize], wx as usize + wy as usize, time)
+}
+
+fn tile_color(tile: i32, seed: usize, time: f64) -> u32 {
+    match tile {
+        TILE_EMPTY => rgb(28, 28, 36),
+        TILE_GRASS => rgb(50, 120, 50),
+        TILE_DOOR => rgb(150, 100, 50),
+        TILE_EXIT => rgb(255, 180, 80),
+        TILE_HAZARD => {
+            let w = 0.5 + 0.5 * (time * 3.0 + seed as f64 * 0.7).sin();
+            rgb(200 + (50.0 * w) as u8, 50 + (70.0 * w) as u8, 20)
+        }
+        _ => rgb(90, 90, 110),
+    }
+}
+
+// Ícono centrado en (x, y) con "radio" s.
+fn draw_icon(buf: &mut [u32], icon: Icon, x: i32, y: i32, s: i32, time: f64) {
+    if let Icon::Item(ItemKind::Badge) = icon {
+        draw_badge_icon(buf, (x - s).max(0) as usize, (y - s).max(0) as usize, (2 * s + 1) as usize);
+        return;
+    }
+    let pulse = (time * 5.0).sin() > 0.0;
+    for dy in -s..=s {
+        for dx in -s..=s {
+            let round = dx * dx + dy * dy <= s * s;
+            let diamond = dx.abs() + dy.abs() <= s;
+            let c = match icon {
+                Icon::Enemy(EnemyKind::Wild) => round.then_some(rgb(230, 50, 50)),
+                Icon::Enemy(EnemyKind::Trainer) => diamond.then_some(rgb(255, 140, 40)),
+                Icon::Enemy(EnemyKind::Leader) => diamond.then(|| if dx.abs() + dy.abs() <= s / 2 && pulse { rgb(120, 60, 0) } else { rgb(255, 215, 80) }),
+                Icon::Item(ItemKind::Potion) => (dx.abs() <= s / 3 || dy.abs() <= s / 3).then_some(rgb(255, 110, 180)),
+                Icon::Item(ItemKind::Key) => (dx.abs().max(dy.abs()) > s / 3).then_some(rgb(255, 230, 60)),
+                Icon::Item(_) => round.then_some(if dy < 0 { rgb(230, 40, 40) } else if dy == 0 { rgb(20, 20, 20) } else { rgb(245, 245, 245) }),
+                Icon::Ghost => (round && dx * dx + dy * dy >= (s - 1) * (s - 1)).then_some(rgb(150, 200, 255)),
+                Icon::Exit => Some(if ((dx + s) * 2 / (s + 1) + (dy + s) * 2 / (s + 1)) % 2 == 0 { rgb(250, 250, 250) } else { rgb(20, 20, 20) }),
+            };
+            if let Some(c) = c && x + dx >= 0 && y + dy >= 0 { put_pixel(buf, (x + dx) as usize, (y + dy) as usize, c); }
+        }
+    }
+}
+
+// Triángulo relleno con la punta hacia (ux, uy).
+fn draw_arrow(buf: &mut [u32], cx: f64, cy: f64, ux: f64, uy: f64, size: f64, color: u32) {
+    let (vx, vy) = (-uy, ux);
+    let tip = (cx + ux * size, cy + uy * size);
+    let a = (cx - ux * size * 0.7 + vx * size * 0.7, cy - uy * size * 0.7 + vy * size * 0.7);
+    let b = (cx - ux * size * 0.7 - vx * size * 0.7, cy - uy * size * 0.7 - vy * size * 0.7);
+    let edge = |p: (f64, f64), q: (f64, f64), x: f64, y: f64| (q.0 - p.0) * (y - p.1) - (q.1 - p.1) * (x - p.0);
+    let r = size.ceil() as i32 + 1;
+    for y in cy as i32 - r..=cy as i32 + r {
+        for x in cx as i32 - r..=cx as i32 + r {
+            let (fx, fy) = (x as f64 + 0.5, y as f64 + 0.5);
+            let (e0, e1, e2) = (edge(tip, a, fx, fy), edge(a, b, fx, fy), edge(b, tip, fx, fy));
+            let inside = (e0 >= 0.0 && e1 >= 0.0 && e2 >= 0.0) || (e0 <= 0.0 && e1 <= 0.0 && e2 <= 0.0);
+            if inside && x >= 0 && y >= 0 { put_pixel(buf, x as usize, y as usize, color); }
+        }
+    }
+}
+
+/// Automapa: el nivel entero con el norte arriba y la leyenda al costado.
+/// Abajo, las teclas que tenga asignadas cada acción del mapa.
+pub fn draw_full(buf: &mut [u32], map: &WorldMap, pose: &Pose, markers: &[Marker], bindings: &Bindings, time: f64) {
+    postfx::apply(buf, Pass::Rect { x0: 0, y0: 0, x1: WIDTH, y1: HEIGHT, color: rgb(0, 0, 0), alpha: 0.8 });
+    let cell = ((HEIGHT - 110) / MAP_H).min((WIDTH - 260) / MAP_W);
+    let (x0, y0) = (40usize, 60usize);
+    draw_text_centered(buf, "MAPA", 18, 3, rgb(255, 255, 255));
+
+    for (y, row) in map.iter().enumerate() {
+        for (x, &t) in row.iter().enumerate() {
+            let c = tile_color(t, x + y, time);
+            for py in 0..cell - 1 {
+                for px in 0..cell - 1 { put_pixel(buf, x0 + x * cell + px, y0 + y * cell + py, c); }
+            }
+        }
+    }
+    let to_px = |wx: f64, wy: f64| ((x0 as f64 + wx * cell as f64) as i32, (y0 as f64 + wy * cell as f64) as i32);
+    for m in markers {
+        let (x, y) = to_px(m.x, m.y);
+        draw_icon(buf, m.icon, x, y, (cell as i32 / 3).max(3), time);
+    }
+    let (px, py) = to_px(pose.x, pose.y);
+    let (fx, fy) = normalized(pose.dir_x, pose.dir_y);
+    draw_arrow(buf, px as f64, py as f64, fx, fy, cell as f64 * 0.6, rgb(255, 255, 255));
+
+    // Leyenda
+    let lx = (x0 + MAP_W * cell + 30) as i32;
+    let mut ly = y0 as i32 + 4;
+    let white = rgb(235, 235, 235);
+    let row = |buf: &mut [u32], ly: &mut i32, label: &str| {
+        draw_text(buf, label, lx + 24, *ly - 3, 1, white);
+        *ly += 22;
+    };
+    draw_arrow(buf, lx as f64 + 7.0, ly as f64, 0.0, -1.0, 7.0, rgb(255, 255, 255));
+    row(buf, &mut ly, "JUGADOR");
+    let icons: [(Icon, &str); 9] = [
+        (Icon::Enemy(EnemyKind::Wild), "SALVAJE"), (Icon::Enemy(EnemyKind::Trainer), "ENTRENADOR"),
+        (Icon::Enemy(EnemyKind::Leader), "LIDER"), (Icon::Item(ItemKind::Key), "LLAVE"),
+        (Icon::Item(ItemKind::Potion), "POCION"), (Icon::Item(ItemKind::Pokeball), "POKEBOLA"),
+        (Icon::Item(ItemKind::Badge), "MEDALLA"), (Icon::Ghost, "FANTASMA"), (Icon::Exit, "META"),
+    ];
+    for (icon, label) in icons {
+        draw_icon(buf, icon, lx + 7, ly, 6, time);
+        row(buf, &mut ly, label);
+    }
+    ly += 8;
+    for (tile, label) in [(1, "PARED"), (TILE_HAZARD, "LAVA"), (TILE_DOOR, "PUERTA"), (TILE_GRASS, "HIERBA")] {
+        let c = tile_color(tile, 0, time);
+        for yy in -6..=6 { for xx in 1..=13 { put_pixel(buf, (lx + xx) as usize, (ly + yy) as usize, c); } }
+        row(buf, &mut ly, label);
+    }
+
+    let key = |a: Action| bindings.primary(a).and_then(key_name);
+    let mut hints = Vec::new();
+    if let Some(k) = key(Action::Automap) { hints.push(format!("{k} CERRAR")); }
+    if let Some(k) = key(Action::MapRotate) { hints.push(format!("{k} GIRAR MINIMAPA")); }
+    match (key(Action::MapZoomIn), key(Action::MapZoomOut)) {
+        (Some(a), Some(b)) => hints.push(format!("{a}/{b} ZOOM")),
+        (Some(k), None) | (None, Some(k)) => hints.push(format!("{k} ZOOM")),
+        (None, None) => {}
+    }
+    draw_text_centered(buf, &hints.join("  •  "), HEIGHT - 30, 2, rgb(200, 200, 200));
+}
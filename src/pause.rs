@@ -0,0 +1,179 @@
+use crate::constants::{rgb, shade, HEIGHT};
+use crate::hud::{draw_text, draw_text_centered, put_pixel};
+use crate::input::{key_name, Action, Bindings, Input, REBINDABLE};
+use crate::settings::{Settings, FOV_RANGE, RENDER_SCALES, SENS_RANGE};
+use minifb::Key;
+
+#[derive(Copy, Clone, PartialEq)]
+enum Screen { Main, Settings, Rebind(Action) }
+
+/// Lo que main tiene que hacer después de una tecla en la pausa.
+#[derive(Copy, Clone, PartialEq)]
+pub enum PauseResult { None, Resume, Restart, QuitToMenu, Changed, SettingsClosed }
+
+const MAIN_OPTIONS: [&str; 4] = ["REANUDAR", "REINICIAR NIVEL", "AJUSTES", "SALIR AL MENU"];
+const SLIDERS: [&str; 5] = ["MUSICA", "EFECTOS", "SENSIBILIDAD", "CAMPO VISUAL", "ESCALA"];
+const SETTINGS_ROWS: usize = SLIDERS.len() + REBINDABLE.len() + 1; // + VOLVER
+
+pub struct PauseMenu {
+    screen: Screen,
+    cursor: usize,
+    backdrop: Vec<u32>, // último frame del juego, oscurecido detrás del menú
+    taken: Option<Action>, // al reasignar: la última tecla apretada ya era de esta acción fija
+}
+
+impl PauseMenu {
+    pub fn new() -> Self {
+        Self { screen: Screen::Main, cursor: 0, backdrop: Vec::new(), taken: None }
+    }
+
+    /// Abre la pausa congelando el frame actual de fondo.
+    pub fn open(&mut self, frame: &[u32]) {
+        self.screen = Screen::Main;
+        self.cursor = 0;
+        self.backdrop = frame.iter().map(|&c| shade(c, 0.35)).collect();
+    }
+
+    /// Una vez por frame mientras está abierta la pausa.
+    pub fn input(&mut self, input: &mut Input, s: &mut Settings) -> PauseResult {
+        use Action::*;
+        match self.screen {
+            Screen::Rebind(action) => {
+                // Escape cancela; las teclas sin nombre y las de acciones fijas se ignoran
+                for &key in input.keys_pressed() {
+                    if key == Key::Escape { self.screen = Screen::Settings; return PauseResult::None; }
+                    if let Some(other) = input.bindings.reserved_by(key) { self.taken = Some(other); continue; }
+                    if key_name(key).is_some() {
+                        input.bindings.set_primary(action, key);
+                        input.bindings.save();
+                        self.screen = Screen::Settings;
+                        return PauseResult::Changed;
+                    }
+                }
+                PauseResult::None
+            }
+            Screen::Main => {
+                let n = MAIN_OPTIONS.len();
+                if input.pressed(Pause) || input.pressed(Cancel) { return PauseResult::Resume; }
+                if input.pressed(Up) { self.cursor = (self.cursor + n - 1) % n; }
+                if input.pressed(Down) { self.cursor = (self.cursor + 1) % n; }
+                if input.pressed(Confirm) {
+                    match self.cursor {
+                        0 => return PauseResult::Resume,
+                        1 => return PauseResult::Restart,
+                        2 => { self.screen = Screen::Settings; self.cursor = 0; }
+                        _ => return PauseResult::QuitToMenu,
+                    }
+                }
+                PauseResult::None
+            }
+            Screen::Settings => {
+                let n = SETTINGS_ROWS;
+                if input.pressed(Pause) || input.pressed(Cancel)
+                    || (input.pressed(Confirm) && self.cursor == n - 1) {
+                    self.screen = Screen::Main; self.cursor = 2;
+                    return PauseResult::SettingsClosed;
+                }
+                if input.pressed(Up) { self.cursor = (self.cursor + n - 1) % n; }
+                if input.pressed(Down) { self.cursor = (self.cursor + 1) % n; }
+                if self.cursor < SLIDERS.len() && (input.pressed(Left) || input.pressed(Right)) {
+                    adjust(s, self.cursor, if input.pressed(Left) { -1.0 } else { 1.0 });
+                    return PauseResult::Changed;
+                }
+                if input.pressed(Confirm) && self.cursor >= SLIDERS.len() {
+                    self.screen = Screen::Rebind(REBINDABLE[self.cursor - SLIDERS.len()]);
+                    self.taken = None;
+                }
+                PauseResult::None
+            }
+        }
+    }
+
+    pub fn draw(&self, buf: &mut [u32], s: &Settings, bindings: &Bindings) {
+        if self.backdrop.len() == buf.len() { buf.copy_from_slice(&self.backdrop); }
+        else { buf.fill(rgb(10, 8, 12)); }
+
+        let gold = rgb(255, 230, 120);
+        let white = rgb(235, 235, 235);
+        match self.screen {
+            Screen::Main => {
+                draw_text_centered(buf, "PAUSA", HEIGHT / 5, 6, gold);
+                for (i, opt) in MAIN_OPTIONS.iter().enumerate() {
+                    let (txt, col) = if i == self.cursor { (format!("> {opt} <"), gold) } else { (opt.to_string(), white) };
+                    draw_text_centered(buf, &txt, HEIGHT * 2 / 5 + i * 50, 4, col);
+                }
+            }
+            Screen::Settings | Screen::Rebind(_) => {
+                draw_text_centered(buf, "AJUSTES", 30, 5, gold);
+                let (x_label, x_value, row_h, y0) = (120, 470, 28usize, 90usize);
+                for row in 0..SETTINGS_ROWS {
+                    let y = y0 + row * row_h;
+                    let sel = row == self.cursor;
+                    let col = if sel { gold } else { white };
+                    if sel { draw_text(buf, ">", x_label - 30, y as i32, 3, gold); }
+
+                    if row < SLIDERS.len() {
+                        draw_text(buf, SLIDERS[row], x_label, y as i32, 3, col);
+                        slider(buf, x_value, y + 4, slider_value(s, row), sel);
+                    } else if row < SLIDERS.len() + REBINDABLE.len() {
+                        let a = REBINDABLE[row - SLIDERS.len()];
+                        draw_text(buf, a.label(), x_label, y as i32, 3, col);
+                        let key = match self.taken {
+                            _ if self.screen != Screen::Rebind(a) => bindings.primary(a).and_then(key_name).unwrap_or("").to_string(),
+                            Some(other) => format!("ES DE {}", other.label()),
+                            None => "APRIETA UNA TECLA".to_string(),
+                        };
+                        draw_text(buf, &key, x_value as i32, y as i32, 3, if self.screen == Screen::Rebind(a) { rgb(120, 220, 255) } else { col });
+                    } else {
+                        draw_text(buf, "VOLVER", x_label, y as i32, 3, col);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Valor 0..1 de cada slider (para dibujar la barra).
+fn slider_value(s: &Settings, row: usize) -> f64 {
+    let norm = |v: f64, (lo, hi): (f64, f64)| ((v - lo) / (hi - lo)).clamp(0.0, 1.0);
+    match row {
+        0 => s.music_volume as f64,
+        1 => s.sfx_volume as f64,
+        2 => norm(s.mouse_sens, SENS_RANGE),
+        3 => norm(s.fov_deg, FOV_RANGE),
+        _ => {
+            // Escala: más a la derecha = más resolución
+            let i = RENDER_SCALES.iter().position(|r| (r - s.render_scale).abs() < 0.01).unwrap_or(0);
+            1.0 - i as f64 / (RENDER_SCALES.len() - 1) as f64
+        }
+    }
+}
+
+// Mueve un slider un paso (10% del rango; la escala salta entre valores fijos).
+fn adjust(s: &mut Settings, row: usize, dir: f64) {
+    let step = |v: f64, (lo, hi): (f64, f64)| (v + dir * (hi - lo) / 10.0).clamp(lo, hi);
+    match row {
+        0 => s.music_volume = step(s.music_volume as f64, (0.0, 1.0)) as f32,
+        1 => s.sfx_volume = step(s.sfx_volume as f64, (0.0, 1.0)) as f32,
+        2 => s.mouse_sens = step(s.mouse_sens, SENS_RANGE),
+        3 => s.fov_deg = step(s.fov_deg, FOV_RANGE).round(),
+        _ => {
+            let i = RENDER_SCALES.iter().position(|r| (r - s.render_scale).abs() < 0.01).unwrap_or(0) as i32;
+            let j = (i - dir as i32).clamp(0, RENDER_SCALES.len() as i32 - 1) as usize;
+            s.render_scale = RENDER_SCALES[j];
+        }
+    }
+}
+
+fn slider(buf: &mut [u32], x0: usize, y0: usize, v: f64, selected: bool) {
+    let (w, h) = (220usize, 12usize);
+    let fill = (v * w as f64) as usize;
+    let on = if selected { rgb(255, 200, 80) } else { rgb(200, 200, 210) };
+    for y in 0..h {
+        for x in 0..w {
+            let edge = y == 0 || y == h - 1 || x == 0 || x == w - 1;
+            let c = if edge { rgb(255, 255, 255) } else if x < fill { on } else { rgb(40, 40, 50) };
+            put_pixel(buf, x0 + x, y0 + y, c);
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::constants::PLAYER_MAX_HP;
 use crate::constants::{MAP_H, MAP_W};
 use crate::world::{is_passable, WorldMap};
+use crate::items::{Inventory, ItemKind, POTION_HEAL};
+use crate::battle::Party;
 
 pub struct Player {
     pub x: f64, pub y: f64,
@@ -8,6 +10,8 @@ pub struct Player {
     pub plane_x: f64, pub plane_y: f64,
     pub hp: i32,
     pub invuln: f64,
+    pub inventory: Inventory,
+    pub party: Party,
 }
 
 impl Player {
@@ -18,6 +22,8 @@ impl Player {
             plane_x: 0.0, plane_y: 0.66,
             hp: PLAYER_MAX_HP,
             invuln: 0.0,
+            inventory: Inventory::default(),
+            party: Party::starter(),
         }
     }
 
@@ -31,6 +37,14 @@ impl Player {
         self.plane_y =  old_plane_x * angle.sin() + self.plane_y * angle.cos();
     }
 
+    /// Ajusta el campo de visión (grados) escalando el plano de cámara.
+    pub fn set_fov(&mut self, deg: f64) {
+        let len = (self.plane_x * self.plane_x + self.plane_y * self.plane_y).sqrt().max(1e-6);
+        let want = (deg.to_radians() * 0.5).tan();
+        self.plane_x *= want / len;
+        self.plane_y *= want / len;
+    }
+
     pub fn try_move(&mut self, speed: f64, dx: f64, dy: f64, map: &WorldMap) {
         let nx = self.x + dx * speed;
         let ny = self.y + dy * speed;
@@ -46,6 +60,10 @@ impl Player {
         }
     }
 
+    pub fn pose(&self) -> Pose {
+        Pose { x: self.x, y: self.y, dir_x: self.dir_x, dir_y: self.dir_y, plane_x: self.plane_x, plane_y: self.plane_y }
+    }
+
     pub fn tick(&mut self, dt: f64) {
         if self.invuln > 0.0 { self.invuln -= dt; }
     }
@@ -55,4 +73,40 @@ impl Player {
             self.invuln = 0.6; // 600 ms de i-frames
         }
     }
+
+    /// Toma una poción del inventario si hace falta curarse.
+    pub fn use_potion(&mut self) -> bool {
+        if self.hp >= PLAYER_MAX_HP || !self.inventory.take(ItemKind::Potion) { return false; }
+        self.hp = (self.hp + POTION_HEAL).min(PLAYER_MAX_HP);
+        true
+    }
+}
+
+/// Posición y cámara del jugador en un tick; el render dibuja entre dos de estas.
+#[derive(Copy, Clone)]
+pub struct Pose {
+    pub x: f64, pub y: f64,
+    pub dir_x: f64, pub dir_y: f64,
+    pub plane_x: f64, pub plane_y: f64,
+}
+
+impl Pose {
+    pub fn rotate(&mut self, angle: f64) {
+        let (s, c) = angle.sin_cos();
+        (self.dir_x, self.dir_y) = (self.dir_x * c - self.dir_y * s, self.dir_x * s + self.dir_y * c);
+        (self.plane_x, self.plane_y) = (self.plane_x * c - self.plane_y * s, self.plane_x * s + self.plane_y * c);
+    }
+
+    /// Interpola de `self` (tick anterior) a `to` (tick actual), `t` en 0..1.
+    /// Si hubo un salto (nivel o partida cargada) se queda con `to`.
+    pub fn lerp(&self, to: &Pose, t: f64) -> Pose {
+        let (dx, dy) = (to.x - self.x, to.y - self.y);
+        if dx * dx + dy * dy > 1.0 || self.dir_x * to.dir_x + self.dir_y * to.dir_y < 0.5 { return *to; }
+        let l = |a: f64, b: f64| a + (b - a) * t;
+        Pose {
+            x: l(self.x, to.x), y: l(self.y, to.y),
+            dir_x: l(self.dir_x, to.dir_x), dir_y: l(self.dir_y, to.dir_y),
+            plane_x: l(self.plane_x, to.plane_x), plane_y: l(self.plane_y, to.plane_y),
+        }
+    }
 }
@@ -0,0 +1,267 @@
+use std::path::Path;
+
+use crate::constants::{rgb, HEIGHT, WIDTH};
+use crate::levels::LevelDef;
+
+pub const POSTFX_PATH: &str = "assets/postfx.cfg";
+
+/// Corrección de color de un nivel: `out = lift + in * gain` por canal y
+/// después saturación (1 = igual, 0 = gris).
+#[derive(Clone, Copy)]
+pub struct Grade {
+    pub lift: [f32; 3],
+    pub gain: [f32; 3],
+    pub sat: f32,
+}
+
+/// Distorsión de pantalla completa según el gimnasio.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Distort { HeatHaze, Underwater }
+
+/// Una pasada sobre el framebuffer. Las de color miran solo su píxel; las de
+/// distorsión leen de una copia del frame (ver `reads_frame`).
+#[derive(Clone, Copy)]
+pub enum Pass {
+    /// Bordes hacia `color`: alpha = inner + slope * d (hasta `max`), con d = 1 a
+    /// `radius` medias diagonales del centro.
+    Vignette { color: u32, inner: f32, slope: f32, max: f32, radius: f32 },
+    /// Rectángulo de `color` encima (paneles detrás de texto).
+    Rect { x0: usize, y0: usize, x1: usize, y1: usize, color: u32, alpha: f32 },
+    Brightness(f32),
+    Grade(Grade),
+    /// Líneas de CRT: una fila de cada dos más oscura.
+    Scanlines(f32),
+    /// Grises: 0 = color, 1 = todo gris.
+    Grayscale(f32),
+    /// Aire caliente: las filas ondulan de costado, más cerca del piso.
+    HeatHaze { amp: f32, time: f32 },
+    /// Bajo el agua: ondula en los dos ejes.
+    Wobble { amp: f32, time: f32 },
+}
+
+// Debajo de este trabajo (píxeles por costo de la pasada) no vale la pena
+// repartir en hilos: arrancarlos cuesta más que hacerlo en este.
+const PARALLEL_MIN: usize = 2 * WIDTH * HEIGHT;
+
+impl Pass {
+    fn reads_frame(&self) -> bool {
+        matches!(self, Pass::HeatHaze { .. } | Pass::Wobble { .. })
+    }
+
+    // Trabajo aproximado: píxeles tocados por lo que cuesta cada uno.
+    fn cost(&self) -> usize {
+        match *self {
+            Pass::Rect { x0, y0, x1, y1, .. } => x1.saturating_sub(x0) * y1.saturating_sub(y0),
+            Pass::Scanlines(_) => WIDTH * HEIGHT / 2,
+            Pass::Brightness(_) => WIDTH * HEIGHT,
+            Pass::Vignette { .. } | Pass::Grade(_) | Pass::Grayscale(_) => 3 * WIDTH * HEIGHT,
+            Pass::HeatHaze { .. } | Pass::Wobble { .. } => 4 * WIDTH * HEIGHT,
+        }
+    }
+}
+
+/// Qué efectos van y con cuánta fuerza (`assets/postfx.cfg`, opcional):
+///   vignette 0.35   crt 0.2   grade 1   distortion 1   threads 0
+/// (0 apaga; `threads 0` = uno por núcleo).
+pub struct PostConfig {
+    pub vignette: f32,
+    pub crt: f32,
+    pub grade: bool,
+    pub distortion: bool,
+    pub threads: usize,
+}
+
+impl PostConfig {
+    pub fn load() -> Self {
+        let mut c = Self { vignette: 0.35, crt: 0.0, grade: true, distortion: true, threads: 0 };
+        let Ok(text) = std::fs::read_to_string(POSTFX_PATH) else { return c; };
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let mut w = line.split_whitespace();
+            let (Some(key), Some(v)) = (w.next(), w.next().and_then(|v| v.parse::<f32>().ok())) else {
+                eprintln!("{POSTFX_PATH}: no entiendo '{line}'");
+                continue;
+            };
+            match key {
+                "vignette" => c.vignette = v.clamp(0.0, 1.0),
+                "crt" => c.crt = v.clamp(0.0, 1.0),
+                "grade" => c.grade = v != 0.0,
+                "distortion" => c.distortion = v != 0.0,
+                "threads" => c.threads = v.max(0.0) as usize,
+                _ => eprintln!("{POSTFX_PATH}: opción desconocida '{key}'"),
+            }
+        }
+        c
+    }
+
+    pub fn uses(path: &Path) -> bool { path == Path::new(POSTFX_PATH) }
+
+    fn thread_count(&self) -> usize {
+        match self.threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()).min(8),
+            n => n.min(16),
+        }
+    }
+}
+
+/// Cadena de post-proceso del juego: configuración más la copia del frame
+/// que usan las distorsiones (se reutiliza entre frames).
+pub struct PostFx {
+    pub cfg: PostConfig,
+    scratch: Vec<u32>,
+}
+
+impl PostFx {
+    pub fn new() -> Self {
+        Self { cfg: PostConfig::load(), scratch: Vec::new() }
+    }
+
+    /// Corre las pasadas en orden, repartidas en franjas de filas entre hilos.
+    /// Las seguidas van juntas en cada franja (un solo reparto de hilos); solo
+    /// se corta antes de una distorsión, que necesita el frame ya terminado.
+    /// Un grupo liviano (las líneas de CRT solas, un panel) va entero en este hilo.
+    pub fn run(&mut self, buf: &mut [u32], passes: &[Pass]) {
+        let threads = self.cfg.thread_count();
+        let band = HEIGHT.div_ceil(threads);
+        let mut rest = passes;
+        while !rest.is_empty() {
+            let n = 1 + rest[1..].iter().position(Pass::reads_frame).unwrap_or(rest.len() - 1);
+            let (group, next) = rest.split_at(n);
+            rest = next;
+            if group[0].reads_frame() {
+                self.scratch.clear();
+                self.scratch.extend_from_slice(buf);
+            }
+            let src = &self.scratch[..];
+            let run_group = |rows: &mut [u32], y0: usize| for pass in group { run_band(pass, rows, y0, src); };
+            if threads == 1 || group.iter().map(Pass::cost).sum::<usize>() < PARALLEL_MIN {
+                run_group(buf, 0);
+                continue;
+            }
+            // Una franja en este hilo y el resto en hilos nuevos
+            std::thread::scope(|s| {
+                let mut bands = buf.chunks_mut(band * WIDTH).enumerate();
+                let first = bands.next();
+                for (i, rows) in bands { s.spawn(move || run_group(rows, i * band)); }
+                if let Some((_, rows)) = first { run_group(rows, 0); }
+            });
+        }
+    }
+}
+
+impl PostFx {
+    /// Sobre la vista 3D, antes del HUD: la distorsión y el color del nivel.
+    pub fn world(&mut self, buf: &mut [u32], level: &LevelDef, time: f64) {
+        let time = time as f32;
+        let mut passes = Vec::new();
+        if self.cfg.distortion {
+            passes.push(match level.distort {
+                Distort::HeatHaze => Pass::HeatHaze { amp: 3.0, time },
+                Distort::Underwater => Pass::Wobble { amp: 2.5, time },
+            });
+        }
+        if self.cfg.grade { passes.push(Pass::Grade(level.grade)); }
+        self.run(buf, &passes);
+    }
+
+    /// Sobre todo lo del juego (HUD incluido): viñeta y flash rojo de daño (0..1).
+    pub fn screen(&mut self, buf: &mut [u32], damage: f32) {
+        let mut passes = Vec::new();
+        let v = self.cfg.vignette;
+        if v > 0.0 {
+            passes.push(Pass::Vignette { color: rgb(0, 0, 0), inner: 0.0, slope: v, max: v, radius: 1.0 });
+        }
+        if damage > 0.0 {
+            let a = 0.85 * damage.min(1.0); // más fuerte en los bordes
+            passes.push(Pass::Vignette { color: rgb(255, 40, 40), inner: 0.5 * a, slope: 0.7 * a, max: a, radius: 1.0 });
+        }
+        self.run(buf, &passes);
+    }
+
+    /// Al final de cada frame, en cualquier pantalla: las líneas de CRT.
+    pub fn finish(&mut self, buf: &mut [u32]) {
+        if self.cfg.crt > 0.0 { self.run(buf, &[Pass::Scanlines(self.cfg.crt)]); }
+    }
+}
+
+/// Una pasada de color en el hilo actual (pantallas de menú, victoria...).
+pub fn apply(buf: &mut [u32], pass: Pass) {
+    debug_assert!(!pass.reads_frame());
+    run_band(&pass, buf, 0, &[]);
+}
+
+#[inline]
+fn channels(c: u32) -> [f32; 3] {
+    [((c >> 16) & 0xFF) as f32, ((c >> 8) & 0xFF) as f32, (c & 0xFF) as f32]
+}
+
+#[inline]
+fn pack(c: [f32; 3]) -> u32 {
+    rgb(c[0].clamp(0.0, 255.0) as u8, c[1].clamp(0.0, 255.0) as u8, c[2].clamp(0.0, 255.0) as u8)
+}
+
+#[inline]
+fn mix(bg: u32, fg: [f32; 3], a: f32) -> u32 {
+    let b = channels(bg);
+    pack([b[0] + (fg[0] - b[0]) * a, b[1] + (fg[1] - b[1]) * a, b[2] + (fg[2] - b[2]) * a])
+}
+
+#[inline]
+fn luma(c: [f32; 3]) -> f32 { 0.299 * c[0] + 0.587 * c[1] + 0.114 * c[2] }
+
+#[inline]
+fn each_pixel(rows: &mut [u32], y0: usize, f: impl Fn(usize, usize, u32) -> u32) {
+    for (i, row) in rows.chunks_mut(WIDTH).enumerate() {
+        for (x, px) in row.iter_mut().enumerate() { *px = f(x, y0 + i, *px); }
+    }
+}
+
+// Filas `y0..` del frame en `rows`; `src` es el frame entero (solo para distorsiones).
+fn run_band(pass: &Pass, rows: &mut [u32], y0: usize, src: &[u32]) {
+    match *pass {
+        Pass::Vignette { color, inner, slope, max, radius } => {
+            let (cx, cy) = (WIDTH as f32 / 2.0, HEIGHT as f32 / 2.0);
+            let r = (cx * cx + cy * cy).sqrt() * radius;
+            let col = channels(color);
+            each_pixel(rows, y0, |x, y, c| {
+                let (dx, dy) = (x as f32 - cx, y as f32 - cy);
+                let a = (inner + slope * (dx * dx + dy * dy).sqrt() / r).clamp(0.0, max);
+                if a <= 0.001 { c } else { mix(c, col, a) }
+            });
+        }
+        Pass::Rect { x0, y0: ry0, x1, y1, color, alpha } => {
+            // Solo las filas y columnas del rectángulo que caen en esta franja
+            let col = channels(color);
+            let (x0, x1) = (x0.min(WIDTH), x1.min(WIDTH));
+            let n = rows.len() / WIDTH;
+            for y in ry0.max(y0)..y1.min(y0 + n) {
+                let row = &mut rows[(y - y0) * WIDTH..][..WIDTH];
+                for px in &mut row[x0.min(x1)..x1] { *px = mix(*px, col, alpha); }
+            }
+        }
+        Pass::Brightness(k) => each_pixel(rows, y0, |_, _, c| { let v = channels(c); pack([v[0] * k, v[1] * k, v[2] * k]) }),
+        Pass::Grade(g) => each_pixel(rows, y0, |_, _, c| {
+            let v = channels(c);
+            let v = [0, 1, 2].map(|i| g.lift[i] + v[i] * g.gain[i]);
+            let l = luma(v);
+            pack(v.map(|ch| l + (ch - l) * g.sat))
+        }),
+        Pass::Scanlines(s) => each_pixel(rows, y0, |_, y, c| {
+            if y % 2 == 1 { let v = channels(c); pack(v.map(|ch| ch * (1.0 - s))) } else { c }
+        }),
+        Pass::Grayscale(t) => each_pixel(rows, y0, |_, _, c| { let v = channels(c); let l = luma(v); mix(c, [l, l, l], t) }),
+        Pass::HeatHaze { amp, time } => each_pixel(rows, y0, |x, y, _| {
+            // más fuerte abajo (el piso de lava) y casi nada en el cielo
+            let k = (y as f32 / HEIGHT as f32 - 0.35).max(0.0) / 0.65;
+            let off = amp * k * (y as f32 * 0.09 + time * 5.0).sin();
+            let sx = (x as f32 + off).round().clamp(0.0, WIDTH as f32 - 1.0) as usize;
+            src[y * WIDTH + sx]
+        }),
+        Pass::Wobble { amp, time } => each_pixel(rows, y0, |x, y, _| {
+            let ox = amp * (y as f32 * 0.035 + time * 2.1).sin();
+            let oy = amp * 0.6 * (x as f32 * 0.03 + time * 1.7).sin();
+            let sx = (x as f32 + ox).round().clamp(0.0, WIDTH as f32 - 1.0) as usize;
+            let sy = (y as f32 + oy).round().clamp(0.0, HEIGHT as f32 - 1.0) as usize;
+            src[sy * WIDTH + sx]
+        }),
+    }
+}
@@ -0,0 +1,105 @@
+use crate::battle::Party;
+use crate::items::{Inventory, ItemKind};
+use crate::levels::LEVELS;
+use crate::save;
+use crate::settings::Settings;
+
+pub const PROFILE_PATH: &str = "profile.txt";
+
+/// Progreso que sobrevive entre partidas: medallas, desbloqueos, mejores tiempos,
+/// opciones y el equipo/inventario con el que se entra a cada gimnasio.
+/// Archivo de texto versionado (ver `save.rs`) para poder editarlo a mano.
+pub struct Profile {
+    badges: Vec<bool>,           // índice = nivel
+    unlocked: usize,             // gimnasios abiertos: 0..=unlocked
+    best_times: Vec<Option<f64>>,
+    best_scores: Vec<Option<i32>>,
+    pub settings: Settings,
+    pub party: Party,
+    pub inventory: Inventory,
+}
+
+impl Profile {
+    pub fn load() -> Self {
+        let mut p = Self {
+            badges: vec![false; LEVELS.len()],
+            unlocked: 0,
+            best_times: vec![None; LEVELS.len()],
+            best_scores: vec![None; LEVELS.len()],
+            settings: Settings::default(),
+            party: Party::starter(),
+            inventory: Inventory::default(),
+        };
+        let Some(lines) = save::read_versioned(PROFILE_PATH) else { return p; };
+        let idx = |l: &Vec<String>| l.get(1).and_then(|n| n.parse::<usize>().ok());
+        for l in &lines {
+            match l[0].as_str() {
+                "badge" => if let Some(i) = idx(l) && i < p.badges.len() { p.badges[i] = true; },
+                "unlocked" => if let Some(i) = idx(l) { p.unlocked = p.unlocked.max(i); },
+                "best" => if let Some(i) = idx(l) && i < p.best_times.len() {
+                    p.best_times[i] = l.get(2).and_then(|t| t.parse().ok());
+                },
+                "score" => if let Some(i) = idx(l) && i < p.best_scores.len() {
+                    p.best_scores[i] = l.get(2).and_then(|t| t.parse().ok());
+                },
+                // opciones; equipo/inventario se leen abajo y lo desconocido se ignora
+                _ => p.settings.read_line(l),
+            }
+        }
+        if let Some(party) = save::read_party(&lines) { p.party = party; }
+        p.inventory = save::read_inventory(&lines);
+        p
+    }
+
+    pub fn save(&self) {
+        let mut out = save::header("perfil: progreso, opciones y equipo");
+        for (i, _) in self.badges.iter().enumerate().filter(|(_, b)| **b) {
+            out.push_str(&format!("badge {i}\n"));
+        }
+        out.push_str(&format!("unlocked {}\n", self.unlocked));
+        for (i, t) in self.best_times.iter().enumerate() {
+            if let Some(t) = t { out.push_str(&format!("best {i} {t:.3}\n")); }
+        }
+        for (i, n) in self.best_scores.iter().enumerate() {
+            if let Some(n) = n { out.push_str(&format!("score {i} {n}\n")); }
+        }
+        self.settings.write(&mut out);
+        save::write_party(&mut out, &self.party);
+        save::write_inventory(&mut out, &self.inventory);
+        if let Err(e) = std::fs::write(PROFILE_PATH, out) {
+            eprintln!("No se pudo guardar el perfil: {e}");
+        }
+    }
+
+    pub fn has_badge(&self, level: usize) -> bool {
+        self.badges.get(level).copied().unwrap_or(false)
+    }
+
+    /// Los gimnasios se abren en orden: el primero siempre, el resto al ganar el anterior.
+    pub fn unlocked(&self, level: usize) -> bool {
+        level <= self.unlocked || (level > 0 && self.has_badge(level - 1))
+    }
+
+    pub fn best_time(&self, level: usize) -> Option<f64> {
+        self.best_times.get(level).copied().flatten()
+    }
+
+    pub fn best_score(&self, level: usize) -> Option<i32> {
+        self.best_scores.get(level).copied().flatten()
+    }
+
+    /// Nivel terminado (se le ganó al líder): medalla, mejor tiempo y puntaje, siguiente gimnasio
+    /// abierto y el equipo/inventario que se lleva. Las llaves son de cada gimnasio.
+    /// Guarda de una vez.
+    pub fn complete(&mut self, level: usize, time: f64, score: i32, party: &Party, inventory: &Inventory) {
+        if level >= self.badges.len() { return; }
+        self.badges[level] = true;
+        if self.best_time(level).is_none_or(|b| time < b) { self.best_times[level] = Some(time); }
+        if self.best_score(level).is_none_or(|b| score > b) { self.best_scores[level] = Some(score); }
+        self.unlocked = self.unlocked.max((level + 1).min(LEVELS.len() - 1));
+        self.party = party.clone();
+        self.inventory = inventory.clone();
+        while self.inventory.take(ItemKind::Key) {}
+        self.save();
+    }
+}
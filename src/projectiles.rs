@@ -0,0 +1,251 @@
+use crate::battle::{capture_chance, field_damage, Creature, Party, PARTY_MAX};
+use crate::constants::rgb;
+use crate::enemies::{Enemy, EnemyKind, EnemyManager, EnemyState};
+use crate::items::{ItemKind, Pickup};
+use crate::player::Player;
+use crate::sprites::{pokeball_fallback, AnimatedSprite, Fallback, SpriteFrame, SpriteSheet};
+use crate::world::{line_of_sight, rand_range, WorldMap};
+use std::rc::Rc;
+
+const BALL_SPEED: f64 = 7.0;    // celdas/s
+const ATTACK_SPEED: f64 = 9.0;
+const GRAVITY: f64 = 3.0;       // la pokébola hace arco
+const HIT_RADIUS: f64 = 0.4;
+const ATTACK_LIFE: f64 = 1.2;   // s antes de disiparse
+const SHAKE_TIME: f64 = 0.6;    // s por sacudida de la pokébola
+const SUBSTEP: f64 = 0.2;       // avance máximo por chequeo (no atravesar paredes finas)
+
+pub enum ProjKind {
+    Pokeball,
+    Attack(Creature), // copia del pokémon que lo lanzó (tipo/nivel para el daño)
+}
+
+struct Projectile {
+    kind: ProjKind,
+    sprite: AnimatedSprite,
+    vx: f64, vy: f64, vz: f64,
+    life: f64,
+}
+
+// Pokébola en el piso con el bicho adentro, sacudiéndose.
+struct Capture {
+    enemy: Enemy,
+    ball: AnimatedSprite,
+    base_x: f64,
+    t: f64,
+    shakes: u32, // sacudidas que faltan
+    caught: bool,
+}
+
+/// Lo que pasó con los proyectiles este frame (sonidos / avisos en main).
+pub enum ProjEvent {
+    Dropped,                  // la pokébola cayó o rebotó: queda en el piso para recogerla
+    Hit(&'static str),
+    Fainted(&'static str),
+    Captured(&'static str),
+    PartyFull,                // la pokébola rebota: no hay lugar en el equipo
+    BrokeFree(&'static str),
+}
+
+pub struct ProjectileManager {
+    list: Vec<Projectile>,
+    captures: Vec<Capture>,
+    ball_sheet: Rc<SpriteSheet>,
+    attack_sheet: Rc<SpriteSheet>,
+    rng: u64,
+}
+
+impl ProjectileManager {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            list: Vec::new(),
+            captures: Vec::new(),
+            ball_sheet: Rc::new(SpriteSheet::load("pokeball", Fallback::Frames(pokeball_fallback, 6.0))),
+            attack_sheet: Rc::new(SpriteSheet::load("attack", Fallback::Frames(gen_orb_frames, 12.0))),
+            rng: seed.rotate_left(7) | 1,
+        }
+    }
+
+    /// Lanza una pokébola hacia donde mira el jugador (en arco).
+    pub fn throw_ball(&mut self, p: &Player) {
+        let mut sprite = AnimatedSprite::new(p.x + p.dir_x * 0.3, p.y + p.dir_y * 0.3, self.ball_sheet.clone());
+        sprite.play("idle");
+        sprite.scale = 0.2;
+        sprite.z = 0.35;
+        self.list.push(Projectile {
+            kind: ProjKind::Pokeball, sprite,
+            vx: p.dir_x * BALL_SPEED, vy: p.dir_y * BALL_SPEED, vz: 1.2, life: f64::INFINITY,
+        });
+    }
+
+    /// El pokémon activo escupe un ataque de su tipo, en línea recta.
+    pub fn fire_attack(&mut self, p: &Player, attacker: Creature) {
+        let mut sprite = AnimatedSprite::new(p.x + p.dir_x * 0.3, p.y + p.dir_y * 0.3, self.attack_sheet.clone());
+        sprite.scale = 0.25;
+        sprite.z = 0.3;
+        sprite.tint = attacker.species.ty.color();
+        self.list.push(Projectile {
+            kind: ProjKind::Attack(attacker), sprite,
+            vx: p.dir_x * ATTACK_SPEED, vy: p.dir_y * ATTACK_SPEED, vz: 0.0, life: ATTACK_LIFE,
+        });
+    }
+
+    /// Mueve proyectiles (DDA contra paredes, radio contra enemigos) y avanza las capturas.
+    /// Las pokébolas que no atrapan nada quedan como objeto en `pickups`.
+    pub fn update(&mut self, dt: f64, map: &WorldMap, enemies: &mut EnemyManager,
+                  party: &mut Party, pickups: &mut Vec<Pickup>) -> Vec<ProjEvent> {
+        let mut events = Vec::new();
+        let mut i = 0;
+        'next: while i < self.list.len() {
+            let pr = &mut self.list[i];
+            pr.sprite.update(dt);
+            pr.life -= dt;
+            pr.sprite.z += pr.vz * dt;
+            if matches!(pr.kind, ProjKind::Pokeball) { pr.vz -= GRAVITY * dt; }
+
+            let (dx, dy) = (pr.vx * dt, pr.vy * dt);
+            let steps = ((dx * dx + dy * dy).sqrt() / SUBSTEP).ceil().max(1.0) as usize;
+            for _ in 0..steps {
+                let (ox, oy) = (pr.sprite.x, pr.sprite.y);
+                let (nx, ny) = (ox + dx / steps as f64, oy + dy / steps as f64);
+
+                // Pared (DDA): la pokébola cae donde estaba, el ataque se deshace
+                if !line_of_sight(map, ox, oy, nx, ny) {
+                    let pr = self.list.swap_remove(i);
+                    if matches!(pr.kind, ProjKind::Pokeball) {
+                        pickups.push(Pickup::new(ItemKind::Pokeball, ox, oy, self.ball_sheet.clone()));
+                        events.push(ProjEvent::Dropped);
+                    }
+                    continue 'next;
+                }
+                pr.sprite.x = nx; pr.sprite.y = ny;
+
+                // Enemigos
+                let hit = enemies.list.iter().position(|e| {
+                    (e.sprite.x - nx).powi(2) + (e.sprite.y - ny).powi(2) <= HIT_RADIUS * HIT_RADIUS
+                });
+                if let Some(ei) = hit {
+                    let pr = self.list.swap_remove(i);
+                    // Capturas en curso cuentan como lugares ya ocupados
+                    let full = party.members.len() + self.captures.len() >= PARTY_MAX;
+                    match pr.kind {
+                        ProjKind::Pokeball if enemies.list[ei].kind == EnemyKind::Wild && full => {
+                            pickups.push(Pickup::new(ItemKind::Pokeball, ox, oy, self.ball_sheet.clone()));
+                            events.push(ProjEvent::PartyFull);
+                        }
+                        ProjKind::Pokeball if enemies.list[ei].kind == EnemyKind::Wild => {
+                            let enemy = enemies.list.remove(ei);
+                            self.start_capture(enemy, nx, ny);
+                        }
+                        // Los entrenadores no se capturan: rebota y cae
+                        ProjKind::Pokeball => {
+                            pickups.push(Pickup::new(ItemKind::Pokeball, ox, oy, self.ball_sheet.clone()));
+                            events.push(ProjEvent::Dropped);
+                        }
+                        ProjKind::Attack(atk) => {
+                            let e = &mut enemies.list[ei];
+                            e.state = EnemyState::Chase; // se enoja
+                            if let Some(c) = e.creature.as_mut() {
+                                c.hp = (c.hp - field_damage(&atk, c)).max(0);
+                                let name = c.name();
+                                if c.fainted() {
+                                    enemies.list.remove(ei);
+                                    events.push(ProjEvent::Fainted(name));
+                                } else {
+                                    events.push(ProjEvent::Hit(name));
+                                }
+                            }
+                        }
+                    }
+                    continue 'next;
+                }
+            }
+
+            // Se acabó el vuelo: la pokébola toca el piso, el ataque se disipa
+            let pr = &self.list[i];
+            if pr.sprite.z <= 0.0 || pr.life <= 0.0 {
+                let pr = self.list.swap_remove(i);
+                if matches!(pr.kind, ProjKind::Pokeball) {
+                    pickups.push(Pickup::new(ItemKind::Pokeball, pr.sprite.x, pr.sprite.y, self.ball_sheet.clone()));
+                    events.push(ProjEvent::Dropped);
+                }
+                continue;
+            }
+            i += 1;
+        }
+
+        // Capturas en curso: sacudidas y luego el veredicto
+        let mut k = 0;
+        while k < self.captures.len() {
+            let c = &mut self.captures[k];
+            c.t += dt;
+            let phase = c.t % SHAKE_TIME;
+            c.ball.x = c.base_x + if phase < SHAKE_TIME * 0.5 { 0.06 * (phase * 40.0).sin() } else { 0.0 };
+            c.ball.update(dt);
+            if c.t < SHAKE_TIME { k += 1; continue; }
+            c.t = 0.0;
+            if c.shakes > 1 { c.shakes -= 1; k += 1; continue; }
+
+            let mut c = self.captures.swap_remove(k);
+            let creature = c.enemy.creature.clone().expect("solo se capturan salvajes");
+            let name = creature.name();
+            if c.caught {
+                party.add(creature);
+                events.push(ProjEvent::Captured(name));
+            } else {
+                // Se escapa enojado
+                c.enemy.state = EnemyState::Chase;
+                enemies.list.push(c.enemy);
+                events.push(ProjEvent::BrokeFree(name));
+            }
+        }
+        events
+    }
+
+    fn start_capture(&mut self, enemy: Enemy, x: f64, y: f64) {
+        let chance = enemy.creature.as_ref().map_or(0.0, capture_chance);
+        let caught = (rand_range(&mut self.rng, 1000) as f64) < chance * 1000.0;
+        // Si falla, se escapa tras 0..2 sacudidas (suspenso barato)
+        let shakes = if caught { 3 } else { 1 + rand_range(&mut self.rng, 3) as u32 };
+        let mut ball = AnimatedSprite::new(x, y, self.ball_sheet.clone());
+        ball.scale = 0.2;
+        ball.z = 0.0;
+        self.captures.push(Capture { enemy, ball, base_x: x, t: 0.0, shakes, caught });
+    }
+
+    pub fn sprites(&self) -> impl Iterator<Item = &AnimatedSprite> {
+        self.list.iter().map(|p| &p.sprite).chain(self.captures.iter().map(|c| &c.ball))
+    }
+
+    /// Hot-reload de la hoja de la pokébola / del ataque.
+    pub fn reload(&mut self, path: &std::path::Path) {
+        if self.ball_sheet.uses(path) { self.ball_sheet = Rc::new(self.ball_sheet.reloaded()); }
+        if self.attack_sheet.uses(path) { self.attack_sheet = Rc::new(self.attack_sheet.reloaded()); }
+        for pr in &mut self.list {
+            let sheet = if matches!(pr.kind, ProjKind::Pokeball) { &self.ball_sheet } else { &self.attack_sheet };
+            if !Rc::ptr_eq(sheet, &pr.sprite.sheet) { pr.sprite.set_sheet(sheet.clone()); }
+        }
+        for c in &mut self.captures {
+            if !Rc::ptr_eq(&self.ball_sheet, &c.ball.sheet) { c.ball.set_sheet(self.ball_sheet.clone()); }
+        }
+    }
+}
+
+// Orbe blanco que late (se tiñe con el color del tipo al lanzarlo).
+fn gen_orb_frames() -> Vec<SpriteFrame> {
+    const S: usize = 32;
+    (0..4).map(|fr| {
+        let mut rgba = vec![0u32; S * S];
+        let r = 10.0 + fr as f64 * 1.5;
+        for y in 0..S {
+            for x in 0..S {
+                let d = ((x as f64 - 15.5).powi(2) + (y as f64 - 15.5).powi(2)).sqrt();
+                if d <= r {
+                    let v = (255.0 * (1.0 - 0.5 * d / r)) as u8;
+                    rgba[y * S + x] = 0xFF00_0000 | rgb(v, v, v);
+                }
+            }
+        }
+        SpriteFrame { w: S, h: S, rgba }
+    }).collect()
+}
@@ -2,11 +2,11 @@
 
 use crate::constants::{
     WIDTH, HEIGHT, MAP_W, MAP_H,
-    rgb, shade, TILE_WALL, TILE_HAZARD,
+    rgb, shade, TILE_WALL, TILE_HAZARD, TILE_DOOR, TILE_GRASS,
 };
 use crate::textures::{TextureSet, Texture};
 use crate::world::WorldMap;
-use crate::sprites::SpriteManager;
+use crate::sprites::AnimatedSprite;
 use std::f64::consts::PI;
 
 #[inline]
@@ -14,36 +14,56 @@ fn clamp_i32(v: i32, lo: i32, hi: i32) -> i32 {
     if v < lo { lo } else if v > hi { hi } else { v }
 }
 
+/// Lo que la cámara hace además de moverse y girar (ver camfx.rs):
+/// `eye` sube/baja el ojo (en unidades de mundo, 0 = a media pared),
+/// `horizon` corre el horizonte en píxeles y `roll` lo inclina (radianes).
+#[derive(Clone, Copy, Default)]
+pub struct Lens {
+    pub eye: f64,
+    pub horizon: f64,
+    pub roll: f64,
+}
+
+impl Lens {
+    /// Fila del horizonte en la columna `x` (con el roll cada columna tiene la suya).
+    #[inline]
+    pub fn horizon_at(&self, x: f64) -> f64 {
+        HEIGHT as f64 / 2.0 + self.horizon + self.roll.tan() * (x - WIDTH as f64 / 2.0)
+    }
+
+    /// Altura del ojo sobre el piso (la pared mide 1).
+    #[inline]
+    fn eye_h(&self) -> f64 { (0.5 + self.eye).clamp(0.05, 0.95) }
+}
+
 /// Solo dibuja el CIELO (parte superior). El piso lo pinta `floorcast`.
-pub fn clear_bg(buf: &mut [u32], sky: &Texture, dir_x: f64, dir_y: f64) {
-    let half = HEIGHT / 2;
+pub fn clear_bg(buf: &mut [u32], sky: &Texture, dir_x: f64, dir_y: f64, lens: &Lens) {
+    let half = HEIGHT as f64 / 2.0;
+    let base = rgb(28, 24, 30); // relleno del piso (será sobrescrito por floorcast)
 
-    // --- CIELO ---
+    // --- CIELO --- (columna por columna: el horizonte puede estar inclinado)
     let angle = dir_y.atan2(dir_x);         // [-PI, PI]
     let u_off = angle / (2.0 * PI);         // [-0.5, 0.5]
-    for y in 0..half {
-        let v = y as f64 / (half as f64);   // [0..1)
-        let row = y * WIDTH;
-        for x in 0..WIDTH {
-            let u = ((x as f64 / WIDTH as f64) + u_off).fract();
-            let uu = if u < 0.0 { u + 1.0 } else { u };
-            let color = sky.sample(uu, v);
-            buf[row + x] = color;
+    for x in 0..WIDTH {
+        let u = ((x as f64 / WIDTH as f64) + u_off).fract();
+        let uu = if u < 0.0 { u + 1.0 } else { u };
+        let h = lens.horizon_at(x as f64);
+        let sky_end = h.clamp(0.0, HEIGHT as f64) as usize;
+        for y in 0..sky_end {
+            let v = ((y as f64 - (h - half)) / half).clamp(0.0, 0.999); // [0..1)
+            buf[y * WIDTH + x] = sky.sample(uu, v);
         }
-    }
-
-    // Relleno base oscuro para el piso (será sobrescrito por floorcast)
-    let base = rgb(28, 24, 30);
-    for y in half..HEIGHT {
-        let row = y * WIDTH;
-        for x in 0..WIDTH { buf[row + x] = base; }
+        for y in sky_end..HEIGHT { buf[y * WIDTH + x] = base; }
     }
 }
 
 /// Floor casting plano por tiles:
 /// - `TILE_HAZARD` (lava) = naranja animado.
+/// - `TILE_GRASS` = hierba alta (briznas que se mecen).
 /// - otros = piso rocoso oscuro.
 /// Debe llamarse **antes** de `raycast` para que las paredes pasen encima.
+///
+/// `col_step` > 1 calcula una de cada N columnas y la repite (escala de render).
 pub fn floorcast(
     buf: &mut [u32],
     map: &WorldMap,
@@ -51,8 +71,10 @@ pub fn floorcast(
     dir_x: f64, dir_y: f64,
     plane_x: f64, plane_y: f64,
     time_sec: f64,
+    col_step: usize,
+    lens: &Lens,
 ) {
-    let half = (HEIGHT / 2) as i32;
+    let col_step = col_step.max(1);
 
     // Rayos a los extremos de la pantalla (izq/der)
     let ray0_x = dir_x - plane_x;
@@ -60,27 +82,29 @@ pub fn floorcast(
     let ray1_x = dir_x + plane_x;
     let ray1_y = dir_y + plane_y;
 
-    let pos_z = (HEIGHT as f64) * 0.5; // distancia a plano de proyección
-
-    for y in half..(HEIGHT as i32) {
-        // Distancia del "scanline" al centro
-        let p = y - half;
-        if p <= 0 { continue; }
-
-        // Distancia del piso a lo largo de ese scanline
-        let row_dist = pos_z / (p as f64);
-
-        // Paso en mundo por pixel en X
-        let step_x = row_dist * (ray1_x - ray0_x) / (WIDTH as f64);
-        let step_y = row_dist * (ray1_y - ray0_y) / (WIDTH as f64);
+    let pos_z = (HEIGHT as f64) * lens.eye_h(); // distancia a plano de proyección
+    // Por columna: su horizonte y la dirección de su rayo
+    let cols: Vec<(f64, f64, f64)> = (0..WIDTH).map(|x| {
+        let cam = x as f64 / WIDTH as f64;
+        (lens.horizon_at(x as f64), ray0_x + (ray1_x - ray0_x) * cam, ray0_y + (ray1_y - ray0_y) * cam)
+    }).collect();
+    // Primera fila con piso (el horizonte más alto de las dos puntas)
+    let top = cols[0].0.min(cols[WIDTH - 1].0).max(0.0) as usize;
+
+    for y in top..HEIGHT {
+        let row_idx = y * WIDTH;
+
+        for x in (0..WIDTH).step_by(col_step) {
+            // Distancia del "scanline" al horizonte de esta columna
+            let (horizon, ray_x, ray_y) = cols[x];
+            let p = y as f64 - horizon;
+            if p <= 0.0 { continue; }
+
+            // Distancia del piso a lo largo de ese scanline y punto en el mundo
+            let row_dist = pos_z / p;
+            let world_x = px + row_dist * ray_x;
+            let world_y = py + row_dist * ray_y;
 
-        // Punto inicial (mundo) del scanline
-        let mut world_x = px + row_dist * ray0_x;
-        let mut world_y = py + row_dist * ray0_y;
-
-        let row_idx = (y as usize) * WIDTH;
-
-        for x in 0..WIDTH {
             let ix = world_x.floor() as i32;
             let iy = world_y.floor() as i32;
 
@@ -102,6 +126,13 @@ pub fn floorcast(
                         let b = (20.0  + 30.0  * heat) as u8;  // 20..50
                         rgb(r, g, b)
                     }
+                    TILE_GRASS => {
+                        // Briznas: bandas finas que se mecen con el tiempo
+                        let sway = (fy * 6.0 + time_sec * 1.5).sin() * 0.08;
+                        let blade = ((fx + sway) * 14.0).sin().abs();
+                        let g = (90.0 + 90.0 * blade) as u8;
+                        rgb((30.0 + 25.0 * blade) as u8, g, (30.0 + 10.0 * blade) as u8)
+                    }
                     _ => {
                         // Basalto oscuro con ruido leve
                         let n = ( (fx * 8.0).sin() * (fy * 8.0).sin() * 0.15 + 0.85 )
@@ -122,37 +153,14 @@ pub fn floorcast(
             let light = (1.15 / (1.0 + 0.10 * dist)).clamp(0.15, 1.0);
             let shaded = shade(color, light);
 
-            let idx = row_idx + x;
-            if idx < buf.len() { buf[idx] = shaded; }
-
-            world_x += step_x;
-            world_y += step_y;
+            let end = (x + col_step).min(WIDTH);
+            buf[row_idx + x..row_idx + end].fill(shaded);
         }
     }
 }
 
-pub fn line(buf: &mut [u32], x0: usize, y0: usize, x1: usize, y1: usize, color: u32) {
-    let mut x0 = x0 as i32;
-    let mut y0 = y0 as i32;
-    let x1 = x1 as i32;
-    let y1 = y1 as i32;
-    let dx = (x1 - x0).abs();
-    let sx = if x0 < x1 { 1 } else { -1 };
-    let dy = -(y1 - y0).abs();
-    let sy = if y0 < y1 { 1 } else { -1 };
-    let mut err = dx + dy;
-    loop {
-        if x0>=0 && x0 < WIDTH as i32 && y0>=0 && y0 < HEIGHT as i32 {
-            buf[y0 as usize * WIDTH + x0 as usize] = color;
-        }
-        if x0 == x1 && y0 == y1 { break; }
-        let e2 = 2 * err;
-        if e2 >= dy { err += dy; x0 += sx; }
-        if e2 <= dx { err += dx; y0 += sy; }
-    }
-}
-
-/// Raycaster de paredes (solo TILE_WALL).
+/// Raycaster de paredes (TILE_WALL y puertas cerradas).
+/// Con `col_step` > 1 se lanza un rayo cada N columnas (más rápido, más pixelado).
 pub fn raycast(
     buf: &mut [u32],
     zbuf: &mut [f64],
@@ -163,8 +171,13 @@ pub fn raycast(
     plane_x: f64, plane_y: f64,
     light_radius: f64,
     ambient: f64,
+    col_step: usize,
+    lens: &Lens,
 ) {
-    for x in 0..WIDTH {
+    let col_step = col_step.max(1);
+    let eye = lens.eye_h();
+    for x in (0..WIDTH).step_by(col_step) {
+        let cols = x..(x + col_step).min(WIDTH);
         let camera_x = 2.0 * (x as f64) / (WIDTH as f64) - 1.0;
         let ray_dir_x = dir_x + plane_x * camera_x;
         let ray_dir_y = dir_y + plane_y * camera_x;
@@ -203,15 +216,15 @@ pub fn raycast(
 
             let cell = map[map_y as usize][map_x as usize];
 
-            if cell == TILE_WALL {
-                hit_tile = TILE_WALL;
+            if cell == TILE_WALL || cell == TILE_DOOR {
+                hit_tile = cell;
             }
             // Lava y salida NO son pared
         }
 
         // Si no golpeó pared, saltamos columna
         if hit_tile == 0 {
-            zbuf[x] = f64::INFINITY;
+            zbuf[cols].fill(f64::INFINITY);
             continue;
         }
 
@@ -221,23 +234,26 @@ pub fn raycast(
             ((map_y as f64 - py) + (1 - step_y) as f64 / 2.0) / ray_dir_y
         }.max(1e-6);
 
-        // Altura y límites de la columna
-        let line_h = ((HEIGHT as f64) / perp_dist) as i32;
-        let draw_start_i = -line_h / 2 + HEIGHT as i32 / 2;
-        let draw_end_i   =  line_h / 2 + HEIGHT as i32 / 2;
+        // Altura y límites de la columna (el ojo a `eye` del piso, la pared mide 1)
+        let unit = (HEIGHT as f64) / perp_dist;
+        let line_h = unit as i32;
+        let horizon = lens.horizon_at(x as f64);
+        let draw_start_i = (horizon - (1.0 - eye) * unit) as i32;
+        let draw_end_i   = (horizon + eye * unit) as i32;
         let start = clamp_i32(draw_start_i, 0, HEIGHT as i32 - 1) as usize;
         let end   = clamp_i32(draw_end_i,   0, HEIGHT as i32 - 1) as usize;
 
+        // Textura (variedad, siempre fuego; las puertas tienen la suya)
+        let use_b = ((map_x + map_y) & 1) == 0;
+        let wall_tex = if hit_tile == TILE_DOOR { &tex.door }
+            else if use_b { &tex.wall_fire_b } else { &tex.wall_fire_a };
+
         // Coord “u” de pared
         let wall_x = if side == 0 { py + perp_dist * ray_dir_y } else { px + perp_dist * ray_dir_x };
         let wall_x = wall_x.fract();
-        let mut tex_x = (wall_x * tex.wall_fire_a.w as f64) as usize;
-        if side == 0 && ray_dir_x > 0.0 { tex_x = tex.wall_fire_a.w.saturating_sub(tex_x + 1); }
-        if side == 1 && ray_dir_y < 0.0 { tex_x = tex.wall_fire_a.w.saturating_sub(tex_x + 1); }
-
-        // Textura (variedad, siempre fuego)
-        let use_b = ((map_x + map_y) & 1) == 0;
-        let wall_tex = if use_b { &tex.wall_fire_b } else { &tex.wall_fire_a };
+        let mut tex_x = ((wall_x * wall_tex.w as f64) as usize).min(wall_tex.w - 1);
+        if side == 0 && ray_dir_x > 0.0 { tex_x = wall_tex.w.saturating_sub(tex_x + 1); }
+        if side == 1 && ray_dir_y < 0.0 { tex_x = wall_tex.w.saturating_sub(tex_x + 1); }
 
         // Paso vertical y tex_pos con start real
         let step = wall_tex.h as f64 / (line_h.max(1) as f64);
@@ -249,71 +265,87 @@ pub fn raycast(
         base_light = base_light.max(ambient);
         if side == 1 { base_light *= 0.7; }
 
-        zbuf[x] = perp_dist;
+        zbuf[cols.clone()].fill(perp_dist);
 
         for y in start..=end {
             let tyi = (tex_pos as i32).clamp(0, wall_tex.h as i32 - 1) as usize;
             tex_pos += step;
             let c = wall_tex.data[tyi * wall_tex.w + tex_x];
             let lit = shade(c, base_light as f64);
-            let idx = y * WIDTH + x;
-            if idx < buf.len() { buf[idx] = lit; }
+            buf[y * WIDTH + cols.start..y * WIDTH + cols.end].fill(lit);
         }
     }
 }
 
+/// Sprites billboard, ordenados de atrás hacia adelante (los lejanos primero),
+/// con escala, altura sobre el piso y tinte por sprite. El zbuffer de paredes
+/// sigue recortando columna por columna.
 pub fn draw_sprites(
     buf: &mut [u32],
     zbuf: &[f64],
     px: f64, py: f64,
     dir_x: f64, dir_y: f64,
     plane_x: f64, plane_y: f64,
-    sprites: &SpriteManager,
+    sprites: &[&AnimatedSprite],
     ambient: f64,
+    lens: &Lens,
 ) {
-    use crate::constants::{shade, alpha_blend};
+    use crate::constants::{shade, tint, alpha_blend};
+
+    // Orden por distancia (al cuadrado), de la más lejana a la más cercana
+    let mut order: Vec<(f64, usize)> = sprites.iter().enumerate()
+        .map(|(i, s)| ((s.x - px).powi(2) + (s.y - py).powi(2), i))
+        .collect();
+    order.sort_by(|a, b| b.0.total_cmp(&a.0));
 
-    for s in &sprites.list {
+    let inv_det = 1.0 / (plane_x * dir_y - dir_x * plane_y);
+    let eye = lens.eye_h();
+
+    for &(dist2, i) in &order {
+        let s = sprites[i];
         let sx = s.x - px;
         let sy = s.y - py;
 
-        let inv_det = 1.0 / (plane_x * dir_y - dir_x * plane_y);
         let transform_x = inv_det * ( dir_y * sx - dir_x * sy);
         let transform_y = inv_det * (-plane_y * sx + plane_x * sy);
 
         if transform_y <= 0.01 { continue; } // detrás del player
 
         let sprite_screen_x = ((WIDTH as f64) / 2.0 * (1.0 + transform_x / transform_y)) as i32;
+        let frame = s.frame_for_view(px, py);
 
-        // Tamaño en pantalla
-        let sprite_h = (HEIGHT as f64 / transform_y) as i32;
-        let sprite_w = (HEIGHT as f64 / transform_y) as i32;
+        // Alto en pantalla según escala; ancho respeta el aspecto del frame.
+        // El ojo está a `eye` del piso: el sprite ocupa [z, z + scale] en altura de mundo.
+        let unit = HEIGHT as f64 / transform_y;
+        let sprite_h = (unit * s.scale) as i32;
+        let sprite_w = (unit * s.scale * frame.w as f64 / frame.h.max(1) as f64) as i32;
+        if sprite_h <= 0 || sprite_w <= 0 { continue; }
 
-        let draw_start_y_i = -sprite_h / 2 + (HEIGHT as i32)/2;
-        let draw_end_y_i   =  sprite_h / 2 + (HEIGHT as i32)/2;
         let draw_start_x_i = -sprite_w / 2 + sprite_screen_x;
         let draw_end_x_i   =  sprite_w / 2 + sprite_screen_x;
 
-        let sy0 = clamp_i32(draw_start_y_i, 0, HEIGHT as i32 - 1) as usize;
-        let sy1 = clamp_i32(draw_end_y_i,   0, HEIGHT as i32 - 1) as usize;
         let sx0 = clamp_i32(draw_start_x_i, 0, WIDTH  as i32 - 1) as usize;
         let sx1 = clamp_i32(draw_end_x_i,   0, WIDTH  as i32 - 1) as usize;
 
-        let frame = s.current();
-        let dist = (sx*sx + sy*sy).sqrt();
+        let dist = dist2.sqrt();
         let light = (1.0 / (1.0 + 0.18*dist)).clamp(ambient, 1.0); // luz simple
 
         for stripe in sx0..=sx1 {
             if stripe >= zbuf.len() || transform_y >= zbuf[stripe] { continue; }
 
-            let tex_x_f =
-                ((stripe as i32 - (-sprite_w/2 + sprite_screen_x)) as f64)
+            let tex_x_f = ((stripe as i32 - draw_start_x_i) as f64)
                 * frame.w as f64 / (sprite_w.max(1) as f64);
             let tex_x = (tex_x_f as i32).clamp(0, frame.w as i32 - 1) as usize;
 
+            // Alto por columna (con el roll el horizonte cambia de una a otra)
+            let draw_start_y_i = (lens.horizon_at(stripe as f64) - (s.z + s.scale - eye) * unit) as i32;
+            let draw_end_y_i   = draw_start_y_i + sprite_h;
+            let sy0 = clamp_i32(draw_start_y_i, 0, HEIGHT as i32 - 1) as usize;
+            let sy1 = clamp_i32(draw_end_y_i,   0, HEIGHT as i32 - 1) as usize;
+
             for y in sy0..=sy1 {
-                let d = (y as i32 * 256 - (HEIGHT as i32)*128 + sprite_h*128) as f64;
-                let tex_y_f = (d * frame.h as f64) / ((sprite_h.max(1) as f64) * 256.0);
+                let tex_y_f = ((y as i32 - draw_start_y_i) as f64)
+                    * frame.h as f64 / (sprite_h.max(1) as f64);
                 let tex_y = (tex_y_f as i32).clamp(0, frame.h as i32 - 1) as usize;
 
                 let (rgb_color, alpha) = frame.sample(
@@ -321,8 +353,9 @@ pub fn draw_sprites(
                     tex_y as f64 / frame.h as f64
                 );
                 if alpha == 0 { continue; }
+                let alpha = if s.alpha == 255 { alpha } else { (alpha as u32 * s.alpha as u32 / 255) as u8 };
 
-                let lit = shade(rgb_color, light);
+                let lit = shade(tint(rgb_color, s.tint), light);
                 let idx = y * WIDTH + stripe;
                 if idx < buf.len() {
                     let bg = buf[idx];
@@ -332,34 +365,3 @@ pub fn draw_sprites(
         }
     }
 }
-
-/// Overlay rojo para feedback de daño (opcional).
-pub fn draw_damage_overlay(buf: &mut [u32], intensity: f32) {
-    if intensity <= 0.0 { return; }
-    let a = intensity.clamp(0.0, 1.0) as f64; // 0..1
-    let cx = (WIDTH / 2) as f64;
-    let cy = (HEIGHT / 2) as f64;
-    let maxd = (cx*cx + cy*cy).sqrt();
-
-    for y in 0..HEIGHT {
-        let dy = y as f64 - cy;
-        for x in 0..WIDTH {
-            let dx = x as f64 - cx;
-            let d = (dx*dx + dy*dy).sqrt() / maxd; // 0 centro, 1 borde
-            let vign = (0.5 + 0.7*d).min(1.0);
-            let alpha = a * vign * 0.85; // más fuerte en bordes
-            if alpha <= 0.001 { continue; }
-
-            let idx = y * WIDTH + x;
-            let bg = buf[idx];
-            let r = ((bg >> 16) & 0xFF) as f64;
-            let g = ((bg >> 8)  & 0xFF) as f64;
-            let b = ( bg        & 0xFF) as f64;
-
-            let nr = r*(1.0 - alpha) + 255.0*alpha;
-            let ng = g*(1.0 - alpha) +  40.0*alpha;
-            let nb = b*(1.0 - alpha) +  40.0*alpha;
-            buf[idx] = rgb(nr as u8, ng as u8, nb as u8);
-        }
-    }
-}
@@ -0,0 +1,152 @@
+use crate::battle::Party;
+use crate::constants::{MAP_H, MAP_W, TICK};
+use crate::ghost::Ghost;
+use crate::input::{Action, Input, TickInput};
+use crate::items::Inventory;
+use crate::levels::LEVELS;
+use crate::player::{Player, Pose};
+use crate::save;
+use crate::sim::{Sim, SimEvent};
+
+pub const REPLAY_PATH: &str = "replay.txt";
+
+/// Repetición: nivel, semilla, equipo de salida y la entrada de cada tick.
+/// Con la simulación a paso fijo esto alcanza para reproducir la partida exacta.
+pub struct Replay {
+    pub level: usize,
+    pub seed: u64,
+    party: Party,
+    inventory: Inventory,
+    pub ticks: Vec<TickInput>,
+    pub ghost: Ghost, // camino recorrido (no va en el archivo; ver ghost.rs)
+}
+
+impl Replay {
+    /// Empieza a grabar (llamar antes del primer tick, con el jugador recién creado).
+    pub fn new(level: usize, seed: u64, p: &Player) -> Self {
+        Self { level, seed, party: p.party.clone(), inventory: p.inventory.clone(), ticks: Vec::new(), ghost: Ghost::new() }
+    }
+
+    /// Graba un tick: la entrada y dónde estaba el jugador al empezarlo. El camino
+    /// del fantasma solo avanza en los ticks de exploración, que son los que cuenta
+    /// `sim.time` (con el que después se lo recorre).
+    pub fn push(&mut self, ti: TickInput, sim: &Sim) {
+        self.ticks.push(ti);
+        if sim.battle.is_none() { self.ghost.record(&sim.player); }
+    }
+
+    /// Jugador tal como salió al nivel.
+    pub fn player(&self) -> Player {
+        let mut p = Player::new();
+        p.party = self.party.clone();
+        p.inventory = self.inventory.clone();
+        p
+    }
+
+    pub fn sim(&self) -> Sim { Sim::new(self.level, self.seed, self.player()) }
+
+    pub fn duration(&self) -> f64 { self.ticks.len() as f64 * TICK }
+
+    // Ticks iguales seguidos van en una sola línea: `t veces down pressed giro`.
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let mut out = save::header("repetición");
+        out.push_str(&format!("level {}\nseed {}\n", self.level, self.seed));
+        save::write_party(&mut out, &self.party);
+        save::write_inventory(&mut out, &self.inventory);
+        let mut i = 0;
+        while i < self.ticks.len() {
+            let t = self.ticks[i];
+            let n = self.ticks[i..].iter().take_while(|o| **o == t).count();
+            out.push_str(&format!("t {n} {} {} {}\n", t.down, t.pressed, t.turn));
+            i += n;
+        }
+        std::fs::write(path, out)
+    }
+
+    pub fn load(path: &str) -> Option<Self> {
+        let lines = save::read_versioned(path)?;
+        let get = |key: &str| lines.iter().find(|l| l[0] == key)?.get(1).cloned();
+        let level: usize = get("level")?.parse().ok()?;
+        if level >= LEVELS.len() { return None; }
+        let mut r = Self {
+            level, seed: get("seed")?.parse().ok()?,
+            party: save::read_party(&lines).unwrap_or_else(Party::starter),
+            inventory: save::read_inventory(&lines),
+            ticks: Vec::new(),
+            ghost: Ghost::new(),
+        };
+        for l in lines.iter().filter(|l| l[0] == "t") {
+            let n: usize = l.get(1)?.parse().ok()?;
+            let t = TickInput { down: l.get(2)?.parse().ok()?, pressed: l.get(3)?.parse().ok()?, turn: l.get(4)?.parse().ok()? };
+            r.ticks.extend(std::iter::repeat_n(t, n));
+        }
+        Some(r)
+    }
+}
+
+/// Cómo terminó una repetición corrida sin ventana.
+pub enum Ending { Won, Died, Unfinished }
+
+/// Corre la repetición entera sin ventana ni audio (para `--verify-replay`).
+/// Devuelve el final y en cuántos ticks llegó.
+pub fn run_headless(r: &Replay) -> (Ending, usize) {
+    let mut sim = r.sim();
+    for (i, t) in r.ticks.iter().enumerate() {
+        for e in sim.step(t) {
+            match e {
+                SimEvent::Won => return (Ending::Won, i + 1),
+                SimEvent::Died => return (Ending::Died, i + 1),
+                _ => {}
+            }
+        }
+    }
+    (Ending::Unfinished, r.ticks.len())
+}
+
+pub const SPEEDS: [f64; 4] = [1.0, 2.0, 4.0, 8.0];
+
+/// Reproducción en ventana: pausa, avance rápido y cámara libre.
+pub struct Playback {
+    pub replay: Replay,
+    pub pos: usize,
+    pub speed: usize, // índice en SPEEDS
+    pub paused: bool,
+    pub free_cam: Option<Pose>,
+}
+
+impl Playback {
+    pub fn new(replay: Replay) -> Self {
+        Self { replay, pos: 0, speed: 0, paused: false, free_cam: None }
+    }
+
+    pub fn next(&mut self) -> Option<TickInput> {
+        let t = self.replay.ticks.get(self.pos).copied()?;
+        self.pos += 1;
+        Some(t)
+    }
+
+    /// Controles de la reproducción (una vez por frame). Devuelve false para salir.
+    pub fn controls(&mut self, input: &Input, player: Pose, turn: f64, dt: f64) -> bool {
+        if input.pressed(Action::Pause) { return false; }
+        if input.pressed(Action::Confirm) { self.paused = !self.paused; }
+        if input.pressed(Action::Right) { self.speed = (self.speed + 1).min(SPEEDS.len() - 1); }
+        if input.pressed(Action::Left) { self.speed = self.speed.saturating_sub(1); }
+        if input.pressed(Action::FreeCam) {
+            self.free_cam = if self.free_cam.is_some() { None } else { Some(player) };
+        }
+
+        // Cámara libre: vuela atravesando paredes con las teclas de moverse
+        if let Some(c) = self.free_cam.as_mut() {
+            let axis = |pos: Action, neg: Action| (input.down(pos) as i32 - input.down(neg) as i32) as f64;
+            let fwd = axis(Action::Forward, Action::Back) * 6.0 * dt;
+            let side = axis(Action::StrafeLeft, Action::StrafeRight) * 6.0 * dt;
+            c.x = (c.x + c.dir_x * fwd - c.dir_y * side).clamp(0.5, MAP_W as f64 - 0.5);
+            c.y = (c.y + c.dir_y * fwd + c.dir_x * side).clamp(0.5, MAP_H as f64 - 0.5);
+            let mut rot = turn;
+            if input.down(Action::TurnLeft) { rot -= 2.8 * dt; }
+            if input.down(Action::TurnRight) { rot += 2.8 * dt; }
+            c.rotate(rot);
+        }
+        true
+    }
+}
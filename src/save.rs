@@ -0,0 +1,203 @@
+use crate::battle::{species_by_name, Creature, Party};
+use crate::constants::{MAP_H, MAP_W};
+use crate::enemies::{EnemyKind, SavedEnemy};
+use crate::input::BINDINGS_PATH;
+use crate::items::{Inventory, ItemKind, ITEM_KINDS};
+use crate::player::Player;
+use crate::score::RunStats;
+use crate::world::{is_passable, WorldMap};
+
+// Formato de texto: una línea `version N` y después `clave valores...`.
+// Las claves que no se conocen se ignoran (así un archivo de una versión más
+// nueva se puede leer igual); los archivos viejos pasan por `migrate`.
+pub const SAVE_VERSION: u32 = 3;
+pub const QUICKSAVE_PATH: &str = "quicksave.txt";
+
+/// Lee un archivo versionado y lo deja en el formato actual.
+/// Devuelve las líneas ya partidas en palabras (sin comentarios ni vacías).
+pub fn read_versioned(path: &str) -> Option<Vec<Vec<String>>> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let mut lines: Vec<Vec<String>> = text.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.split_whitespace().map(str::to_string).collect())
+        .collect();
+
+    // Sin línea de versión = v1 (los perfiles de antes solo tenían `badge N`)
+    let mut version = 1;
+    if let Some(i) = lines.iter().position(|l| l[0] == "version") {
+        version = lines[i].get(1).and_then(|v| v.parse().ok()).unwrap_or(1);
+        lines.remove(i);
+    }
+    if version > SAVE_VERSION {
+        eprintln!("{path}: versión {version} más nueva que {SAVE_VERSION}; se lee lo que se entienda");
+    }
+    while version < SAVE_VERSION {
+        migrate(version, &mut lines);
+        version += 1;
+    }
+    Some(lines)
+}
+
+// Un paso de migración: de `from` a `from + 1`.
+fn migrate(from: u32, lines: &mut Vec<Vec<String>>) {
+    if from == 1 {
+        // v1 → v2: los desbloqueos pasan a guardarse explícitos (antes salían de las medallas)
+        let top = lines.iter()
+            .filter(|l| l[0] == "badge")
+            .filter_map(|l| l.get(1)?.parse::<usize>().ok())
+            .max();
+        if let Some(b) = top { lines.push(vec!["unlocked".into(), (b + 1).to_string()]); }
+    }
+    if from == 2 {
+        // v2 → v3: las teclas (`bind accion TECLA`) se mudan a input.cfg
+        let binds: Vec<String> = lines.iter()
+            .filter(|l| l[0] == "bind" && l.len() >= 3)
+            .map(|l| format!("{} = {}\n", l[1], l[2]))
+            .collect();
+        lines.retain(|l| l[0] != "bind");
+        if !binds.is_empty() && !std::path::Path::new(BINDINGS_PATH).exists() {
+            let _ = std::fs::write(BINDINGS_PATH, binds.concat());
+        }
+    }
+}
+
+pub fn header(what: &str) -> String {
+    format!("# {what}\nversion {SAVE_VERSION}\n")
+}
+
+// ===================== Equipo e inventario =====================
+
+pub fn write_party(out: &mut String, party: &Party) {
+    out.push_str(&format!("party {}\n", party.active));
+    for c in &party.members {
+        out.push_str(&format!("mon {} {} {}\n", c.name(), c.level, c.hp));
+    }
+}
+
+pub fn parse_creature(words: &[String]) -> Option<Creature> {
+    let sp = species_by_name(words.first()?)?;
+    let mut c = Creature::new(sp, words.get(1)?.parse().ok()?);
+    c.hp = words.get(2).and_then(|h| h.parse().ok()).unwrap_or(c.max_hp).clamp(0, c.max_hp);
+    Some(c)
+}
+
+/// `None` si no hay equipo guardado (o quedó vacío por especies desconocidas).
+pub fn read_party(lines: &[Vec<String>]) -> Option<Party> {
+    let members: Vec<Creature> = lines.iter()
+        .filter(|l| l[0] == "mon")
+        .filter_map(|l| parse_creature(&l[1..]))
+        .collect();
+    if members.is_empty() { return None; }
+    let active = lines.iter().find(|l| l[0] == "party")
+        .and_then(|l| l.get(1)?.parse::<usize>().ok())
+        .unwrap_or(0)
+        .min(members.len() - 1);
+    Some(Party { members, active })
+}
+
+pub fn write_inventory(out: &mut String, inv: &Inventory) {
+    for kind in ITEM_KINDS {
+        let n = inv.count(kind);
+        if n > 0 { out.push_str(&format!("item {} {n}\n", kind.id())); }
+    }
+}
+
+pub fn read_inventory(lines: &[Vec<String>]) -> Inventory {
+    let mut inv = Inventory::default();
+    for l in lines.iter().filter(|l| l[0] == "item") {
+        let Some(kind) = l.get(1).and_then(|k| ItemKind::from_id(k)) else { continue; };
+        let n: u32 = l.get(2).and_then(|n| n.parse().ok()).unwrap_or(0);
+        for _ in 0..n { inv.add(kind); }
+    }
+    inv
+}
+
+// ===================== Quicksave (a mitad de nivel) =====================
+
+pub struct QuickSave {
+    pub level: usize,
+    pub time: f64,
+    pub stats: RunStats,
+    pub player: Player,
+    pub map: WorldMap,
+    pub items: Vec<(ItemKind, f64, f64)>,
+    pub enemies: Vec<SavedEnemy>,
+}
+
+/// Guarda todo lo del nivel en curso. Los proyectiles en vuelo no se guardan.
+pub fn write_quicksave(qs: &QuickSave) -> std::io::Result<()> {
+    let p = &qs.player;
+    let mut out = header("quicksave");
+    out.push_str(&format!("level {}\ntime {:.3}\n", qs.level, qs.time));
+    let st = &qs.stats;
+    out.push_str(&format!("stats {} {} {}\n", st.damage, st.hazard_tiles, st.steps));
+    out.push_str(&format!("player {:.4} {:.4} {:.6} {:.6} {:.6} {:.6} {}\n",
+        p.x, p.y, p.dir_x, p.dir_y, p.plane_x, p.plane_y, p.hp));
+    write_party(&mut out, &p.party);
+    write_inventory(&mut out, &p.inventory);
+    for row in &qs.map {
+        let cells: Vec<String> = row.iter().map(|t| t.to_string()).collect();
+        out.push_str(&format!("row {}\n", cells.join(" ")));
+    }
+    for &(kind, x, y) in &qs.items {
+        out.push_str(&format!("pickup {} {x:.3} {y:.3}\n", kind.id()));
+    }
+    for e in &qs.enemies {
+        out.push_str(&format!("enemy {} {:.3} {:.3} {}", e.kind.id(), e.x, e.y, e.chasing as u8));
+        if let Some(c) = &e.creature { out.push_str(&format!(" {} {} {}", c.name(), c.level, c.hp)); }
+        out.push('\n');
+    }
+    std::fs::write(QUICKSAVE_PATH, out)
+}
+
+pub fn read_quicksave() -> Option<QuickSave> {
+    let lines = read_versioned(QUICKSAVE_PATH)?;
+    let get = |key: &str| lines.iter().find(|l| l[0] == key);
+    let num = |w: &Vec<String>, i: usize| -> Option<f64> { w.get(i)?.parse().ok() };
+
+    let level = get("level")?.get(1)?.parse().ok()?;
+    let time = get("time").and_then(|l| num(l, 1)).unwrap_or(0.0);
+    // Quicksaves viejos no traen stats: arrancan en cero
+    let stats = get("stats").and_then(|l| Some(RunStats {
+        damage: l.get(1)?.parse().ok()?, hazard_tiles: l.get(2)?.parse().ok()?, steps: l.get(3)?.parse().ok()?,
+    })).unwrap_or_default();
+
+    let mut player = Player::new();
+    let pl = get("player")?;
+    player.x = num(pl, 1)?; player.y = num(pl, 2)?;
+    player.dir_x = num(pl, 3)?; player.dir_y = num(pl, 4)?;
+    player.plane_x = num(pl, 5)?; player.plane_y = num(pl, 6)?;
+    player.hp = pl.get(7)?.parse().ok()?;
+    if let Some(party) = read_party(&lines) { player.party = party; }
+    player.inventory = read_inventory(&lines);
+
+    // Mapa completo (puertas abiertas, hierba, etc.); si está incompleto, no se carga
+    let rows: Vec<&Vec<String>> = lines.iter().filter(|l| l[0] == "row").collect();
+    if rows.len() != MAP_H { return None; }
+    let mut map = [[0i32; MAP_W]; MAP_H];
+    for (y, r) in rows.iter().enumerate() {
+        if r.len() != MAP_W + 1 { return None; }
+        for x in 0..MAP_W { map[y][x] = r[x + 1].parse().ok()?; }
+    }
+
+    // Posiciones fuera del mapa (o el jugador dentro de una pared) harían
+    // reventar el primer `map[y][x]`: el quicksave no se carga y esos objetos
+    // o enemigos se descartan
+    let inside = |x: f64, y: f64| x >= 0.0 && y >= 0.0 && x < MAP_W as f64 && y < MAP_H as f64;
+    if !inside(player.x, player.y) || !is_passable(map[player.y as usize][player.x as usize]) { return None; }
+
+    let items = lines.iter().filter(|l| l[0] == "pickup").filter_map(|l| {
+        Some((ItemKind::from_id(l.get(1)?)?, num(l, 2)?, num(l, 3)?))
+    }).filter(|&(_, x, y)| inside(x, y)).collect();
+    let enemies = lines.iter().filter(|l| l[0] == "enemy").filter_map(|l| {
+        Some(SavedEnemy {
+            kind: EnemyKind::from_id(l.get(1)?)?,
+            x: num(l, 2)?, y: num(l, 3)?,
+            chasing: l.get(4).map(|c| c == "1").unwrap_or(false),
+            creature: l.get(5..).and_then(parse_creature),
+        })
+    }).filter(|e| inside(e.x, e.y)).collect();
+
+    Some(QuickSave { level, time, stats, player, map, items, enemies })
+}
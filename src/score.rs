@@ -0,0 +1,37 @@
+use crate::levels::LEVELS;
+
+/// Lo que se cuenta durante una corrida (además del cronómetro de `Sim`).
+#[derive(Clone, Copy, Default)]
+pub struct RunStats {
+    pub damage: i32,        // vida perdida (lava y golpes)
+    pub hazard_tiles: u32,  // casillas de lava pisadas
+    pub steps: u32,         // pasos (los mismos que suenan)
+}
+
+/// Puntaje de un nivel terminado, con el desglose para la pantalla de victoria.
+pub struct Score {
+    pub lines: Vec<(&'static str, String, i32)>, // concepto, cuánto, puntos
+    pub total: i32,
+    pub rank: char,
+    pub stars: usize,
+}
+
+// Puntos mínimos para cada rango (S da 3 estrellas, A 2, B 1)
+const RANKS: [(i32, char); 4] = [(1400, 'S'), (1100, 'A'), (800, 'B'), (500, 'C')];
+
+/// Tiempo: 1000 en el tiempo par del nivel, más si vas más rápido (tope 2000).
+/// Se resta por daño, por lava y por pasos; la medalla del líder suma aparte.
+pub fn score(level: usize, time: f64, stats: &RunStats) -> Score {
+    let par = LEVELS[level].par;
+    let lines = vec![
+        ("TIEMPO", format!("{time:.2}"), (1000.0 * par / time.max(1.0)).min(2000.0) as i32),
+        ("DAÑO", stats.damage.to_string(), -5 * stats.damage),
+        ("LAVA", stats.hazard_tiles.to_string(), -25 * stats.hazard_tiles as i32),
+        ("PASOS", stats.steps.to_string(), -(stats.steps as i32)),
+        ("MEDALLA", String::new(), 500),
+    ];
+    let total = lines.iter().map(|(_, _, n)| n).sum::<i32>().max(0);
+    let rank = RANKS.iter().find(|(min, _)| total >= *min).map_or('D', |(_, r)| *r);
+    let stars = match rank { 'S' => 3, 'A' => 2, 'B' => 1, _ => 0 };
+    Score { lines, total, rank, stars }
+}
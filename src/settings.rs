@@ -0,0 +1,65 @@
+pub const MOUSE_SENS: f64 = 0.004; // valor por defecto (rad por pixel)
+
+/// Opciones del jugador que se recuerdan entre sesiones (van en el perfil).
+#[derive(Clone)]
+pub struct Settings {
+    pub muted: bool,
+    pub music_volume: f32, // 0..1
+    pub sfx_volume: f32,   // 0..1
+    pub mouse_sens: f64,
+    pub fov_deg: f64,
+    pub render_scale: f64, // 1.0 = resolución completa; 0.5 = columnas al doble de ancho
+    pub map_rotate: bool,  // minimapa girando con el jugador (si no, el norte arriba)
+    pub map_zoom: f64,     // píxeles por casilla en el minimapa
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            music_volume: 0.7,
+            sfx_volume: 0.9,
+            mouse_sens: MOUSE_SENS,
+            fov_deg: 66.0,
+            render_scale: 1.0,
+            map_rotate: true,
+            map_zoom: 6.0,
+        }
+    }
+}
+
+pub const FOV_RANGE: (f64, f64) = (50.0, 110.0);
+pub const SENS_RANGE: (f64, f64) = (0.001, 0.012);
+pub const RENDER_SCALES: [f64; 4] = [1.0, 0.5, 0.33, 0.25];
+pub const MAP_ZOOM_RANGE: (f64, f64) = (3.0, 16.0);
+
+impl Settings {
+    /// Columnas de pantalla por rayo (1 = todas).
+    pub fn column_step(&self) -> usize {
+        (1.0 / self.render_scale.max(0.1)).round().max(1.0) as usize
+    }
+
+    /// Para el perfil: una línea por opción.
+    pub fn write(&self, out: &mut String) {
+        out.push_str(&format!("muted {}\n", self.muted as u8));
+        out.push_str(&format!("music {:.2}\nsfx {:.2}\n", self.music_volume, self.sfx_volume));
+        out.push_str(&format!("sens {:.5}\nfov {:.0}\nscale {:.2}\n", self.mouse_sens, self.fov_deg, self.render_scale));
+        out.push_str(&format!("map_rotate {}\nmap_zoom {:.1}\n", self.map_rotate as u8, self.map_zoom));
+    }
+
+    /// Lee una línea del perfil (las que no son opciones se ignoran).
+    pub fn read_line(&mut self, l: &[String]) {
+        let val = || l.get(1).and_then(|v| v.parse::<f64>().ok());
+        match l[0].as_str() {
+            "muted" => self.muted = l.get(1).is_some_and(|v| v == "1"),
+            "music" => if let Some(v) = val() { self.music_volume = v.clamp(0.0, 1.0) as f32; },
+            "sfx" => if let Some(v) = val() { self.sfx_volume = v.clamp(0.0, 1.0) as f32; },
+            "sens" => if let Some(v) = val() { self.mouse_sens = v.clamp(SENS_RANGE.0, SENS_RANGE.1); },
+            "fov" => if let Some(v) = val() { self.fov_deg = v.clamp(FOV_RANGE.0, FOV_RANGE.1); },
+            "scale" => if let Some(v) = val() { self.render_scale = v.clamp(0.25, 1.0); },
+            "map_rotate" => self.map_rotate = l.get(1).is_some_and(|v| v == "1"),
+            "map_zoom" => if let Some(v) = val() { self.map_zoom = v.clamp(MAP_ZOOM_RANGE.0, MAP_ZOOM_RANGE.1); },
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::battle::{self, Battle, BattleInput, BattleKind, Outcome, PARTY_MAX};
+use crate::constants::{TICK, TILE_DOOR, TILE_EMPTY, TILE_EXIT, TILE_GRASS, TILE_HAZARD};
+use crate::enemies::{Contact, EnemyKind, EnemyManager};
+use crate::input::{Action, TickInput};
+use crate::items::{self, ItemKind, POTION_HEAL};
+use crate::levels::{self, build_level, Goal, LEVELS};
+use crate::player::Player;
+use crate::projectiles::{ProjEvent, ProjectileManager};
+use crate::save::QuickSave;
+use crate::score::RunStats;
+use crate::sprites::SpriteManager;
+use crate::world::{rand_range, WorldMap};
+
+pub const WALK_SPEED: f64 = 4.0;  // casillas por segundo
+pub const STEP_EVERY: f64 = 0.38; // segundos entre pasos caminando
+const SPRINT: f64 = 1.6;          // velocidad al correr (también acelera los pasos)
+
+/// Lo que pasó en un tick y main tiene que mostrar o sonar.
+pub enum SimEvent {
+    Pickup,          // sonido de recoger (objeto, puerta, poción, captura)
+    Step,            // sonido de paso
+    Hurt,            // flash rojo
+    Notice(String),  // aviso en pantalla (y en el título)
+    BattleStart,
+    BattleEnd,
+    Split(usize),    // pasó el punto de control N (tiempo en `splits[N]`)
+    Won,             // le ganó al líder (y se lleva su medalla)
+    Died,
+}
+
+/// Todo lo que avanza con los ticks dentro de un nivel: mapa, jugador, objetos,
+/// enemigos, proyectiles y combates. No toca ventana, audio ni perfil, así que
+/// con la misma semilla y las mismas entradas da siempre lo mismo (repeticiones).
+pub struct Sim {
+    pub level: usize,
+    pub seed: u64,
+    pub map: WorldMap,
+    pub player: Player,
+    pub sprites: SpriteManager,
+    pub enemies: EnemyManager,
+    pub projectiles: ProjectileManager,
+    pub battle: Option<Battle>,
+    battle_enemy: Option<usize>, // entrenador a retirar si ganas
+    rng: u64,                    // encuentros y semillas de combate
+    last_cell: (usize, usize),
+    pub time: f64,               // cronómetro del nivel
+    checkpoints: Vec<(f64, f64)>,
+    pub splits: Vec<f64>,        // tiempo al pasar cada punto de control
+    pub stats: RunStats,         // daño, lava y pasos para el puntaje
+    step_timer: f64,
+    hazard_tick: f64,
+    attack_cd: f64,
+}
+
+impl Sim {
+    /// Nivel nuevo: todo lo aleatorio (mapa, objetos, enemigos, combates) sale de `seed`.
+    pub fn new(level: usize, seed: u64, player: Player) -> Self {
+        let start = (player.x, player.y);
+        let map = build_level(level, seed, start);
+        let mut sprites = SpriteManager::new_fire_gym(); // cámbialo si tienes sprites por nivel
+        sprites.items = items::spawn(&levels::item_spawns(level, &map, seed, start));
+        let enemies = EnemyManager::spawn(&levels::enemy_spawns(level, &map, seed, start), seed, LEVELS[level].element);
+        let key = sprites.items.iter().find(|it| it.kind == ItemKind::Key).map(|it| (it.sprite.x, it.sprite.y));
+        let checkpoints = levels::checkpoints(&map, start, key);
+        let mut s = Self::with(level, map, player, sprites, enemies, seed);
+        s.checkpoints = checkpoints;
+        s
+    }
+
+    /// Sigue un quicksave (los proyectiles en vuelo no se guardan, y los
+    /// parciales solo cuentan en corridas desde el inicio).
+    pub fn restore(qs: QuickSave, seed: u64) -> Self {
+        let mut sprites = SpriteManager::new_fire_gym();
+        sprites.items = items::spawn(&qs.items);
+        let enemies = EnemyManager::restore(qs.enemies, seed);
+        let mut s = Self::with(qs.level, qs.map, qs.player, sprites, enemies, seed);
+        s.time = qs.time;
+        s.stats = qs.stats;
+        s
+    }
+
+    fn with(level: usize, map: WorldMap, player: Player, sprites: SpriteManager, enemies: EnemyManager, seed: u64) -> Self {
+        Self {
+            level, seed, map, player, sprites, enemies,
+            projectiles: ProjectileManager::new(seed),
+            battle: None, battle_enemy: None,
+            rng: seed.rotate_left(29) | 1,
+            last_cell: (usize::MAX, usize::MAX),
+            time: 0.0, checkpoints: Vec::new(), splits: Vec::new(), stats: RunStats::default(), step_timer: 0.0, hazard_tick: 0.0, attack_cd: 0.0,
+        }
+    }
+
+    pub fn snapshot(&self) -> QuickSave {
+        let p = &self.player;
+        QuickSave {
+            level: self.level, time: self.time, stats: self.stats,
+            player: Player { party: p.party.clone(), inventory: p.inventory.clone(), ..*p },
+            map: self.map,
+            items: self.sprites.items.iter().map(|it| (it.kind, it.sprite.x, it.sprite.y)).collect(),
+            enemies: self.enemies.snapshot(),
+        }
+    }
+
+    /// En "recolectar todo": cuántos objetos faltan (None si el nivel no lo pide).
+    pub fn remaining(&self) -> Option<usize> {
+        (LEVELS[self.level].goal == Goal::CollectAll).then_some(self.sprites.items.len())
+    }
+
+    /// Qué le falta a la meta: el gimnasio solo se gana venciendo al líder (que
+    /// espera al lado), así que pisarla nunca alcanza.
+    pub fn exit_blocked(&self) -> String {
+        match self.remaining() {
+            Some(n) if n > 0 => format!("Meta cerrada: {}", faltan(n)),
+            _ => "Meta cerrada: vence al líder".to_string(),
+        }
+    }
+
+    /// Avanza un tick (TICK segundos) con la entrada dada.
+    pub fn step(&mut self, ti: &TickInput) -> Vec<SimEvent> {
+        let mut ev = Vec::new();
+        if self.battle.is_some() { self.step_battle(ti, &mut ev); } else { self.step_explore(ti, &mut ev); }
+        ev
+    }
+
+    /// Un tick del mundo sin el jugador (ya muerto): enemigos, proyectiles y
+    /// sprites siguen. Para la cámara lenta de la muerte; no da eventos.
+    pub fn step_world(&mut self) {
+        self.sprites.update(TICK);
+        self.enemies.update(TICK, &self.map, &mut self.player);
+        self.projectiles.update(TICK, &self.map, &mut self.enemies, &mut self.player.party, &mut self.sprites.items);
+    }
+
+    fn step_explore(&mut self, ti: &TickInput, ev: &mut Vec<SimEvent>) {
+        let p = &mut self.player;
+        self.time += TICK;
+        p.tick(TICK);
+        if self.attack_cd > 0.0 { self.attack_cd -= TICK; }
+
+        // Mouse horizontal (ya en radianes)
+        p.rotate(ti.turn as f64);
+
+        // Correr (Shift) solo acelera hacia adelante: de costado y hacia atrás se camina
+        let sprinting = ti.down(Action::Sprint) && ti.down(Action::Forward);
+        let move_speed = WALK_SPEED * TICK;
+        let rot_speed  = 2.8 * TICK;
+
+        // Movimiento
+        let mut moving = false;
+        let fwd_speed = if sprinting { move_speed * SPRINT } else { move_speed };
+        if ti.down(Action::Forward) { p.try_move(fwd_speed,  p.dir_x,  p.dir_y, &self.map); moving = true; }
+        if ti.down(Action::Back) { p.try_move(-move_speed, p.dir_x,  p.dir_y, &self.map); moving = true; }
+        let px = -p.dir_y; let py = p.dir_x;
+        if ti.down(Action::StrafeLeft) { p.try_move(move_speed,  px,  py, &self.map); moving = true; }
+        if ti.down(Action::StrafeRight) { p.try_move(move_speed, -px, -py, &self.map); moving = true; }
+
+        if ti.down(Action::TurnLeft)  { p.rotate(-rot_speed); }
+        if ti.down(Action::TurnRight) { p.rotate( rot_speed); }
+
+        // Puertas: si empujas una y tienes llave, se abre (la llave se gasta)
+        let fx = (p.x + p.dir_x * 0.7) as usize; let fy = (p.y + p.dir_y * 0.7) as usize;
+        if ti.down(Action::Forward) && self.map[fy][fx] == TILE_DOOR {
+            if p.inventory.take(ItemKind::Key) {
+                self.map[fy][fx] = TILE_EMPTY;
+                ev.push(SimEvent::Pickup);
+                ev.push(SimEvent::Notice("Puerta abierta (-1 llave)".to_string()));
+            } else {
+                ev.push(SimEvent::Notice("Puerta cerrada: necesitas una llave".to_string()));
+            }
+        }
+
+        // Recoger objetos al pasar encima
+        for kind in self.sprites.collect(p.x, p.y, 0.5) {
+            p.inventory.add(kind);
+            ev.push(SimEvent::Pickup);
+            ev.push(SimEvent::Notice(format!("{} recogida", kind.name())));
+        }
+
+        // E: tomar poción
+        if ti.pressed(Action::Potion) {
+            let had = p.inventory.count(ItemKind::Potion) > 0;
+            if p.use_potion() {
+                ev.push(SimEvent::Pickup);
+                ev.push(SimEvent::Notice(format!("Usaste una poción (+{POTION_HEAL} PS)")));
+            } else {
+                let msg = if had { "Ya tienes la vida llena" } else { "No te quedan pociones" };
+                ev.push(SimEvent::Notice(msg.to_string()));
+            }
+        }
+
+        // F: lanzar pokébola (si hay) • Q: ataque del pokémon activo
+        if ti.pressed(Action::Throw) && p.inventory.take(ItemKind::Pokeball) { self.projectiles.throw_ball(p); }
+        if ti.down(Action::Attack) && self.attack_cd <= 0.0 && !p.party.active().fainted() {
+            self.projectiles.fire_attack(p, p.party.active().clone());
+            self.attack_cd = 0.5;
+        }
+        for e in self.projectiles.update(TICK, &self.map, &mut self.enemies, &mut p.party, &mut self.sprites.items) {
+            let msg = match e {
+                ProjEvent::Dropped => continue,
+                ProjEvent::Hit(name) => format!("{name} recibió el golpe"),
+                ProjEvent::Fainted(name) => format!("{name} se debilitó"),
+                ProjEvent::Captured(name) => { ev.push(SimEvent::Pickup); format!("¡{name} capturado! Se unió al equipo") }
+                ProjEvent::PartyFull => format!("Equipo lleno: no entran más de {PARTY_MAX}"),
+                ProjEvent::BrokeFree(name) => format!("{name} se escapó de la pokébola"),
+            };
+            ev.push(SimEvent::Notice(msg));
+        }
+
+        if moving {
+            self.step_timer += TICK;
+            if self.step_timer > STEP_EVERY / if sprinting { SPRINT } else { 1.0 } {
+                ev.push(SimEvent::Step);
+                self.stats.steps += 1;
+                self.step_timer = 0.0;
+            }
+        } else { self.step_timer = 0.0; }
+
+        // Parciales: el siguiente punto de control, en orden
+        if let Some(&(cx, cy)) = self.checkpoints.get(self.splits.len())
+            && (p.x - cx).powi(2) + (p.y - cy).powi(2) < 1.5 * 1.5
+        {
+            self.splits.push(self.time);
+            ev.push(SimEvent::Split(self.splits.len() - 1));
+        }
+
+        // Daño por hazard (lava)
+        let tx = p.x as usize; let ty = p.y as usize;
+        let new_cell = (tx, ty) != self.last_cell;
+        self.last_cell = (tx, ty);
+        let hp_before = p.hp;
+        if self.map[ty][tx] == TILE_HAZARD {
+            if new_cell {
+                self.stats.hazard_tiles += 1;
+                ev.push(SimEvent::Notice("¡La lava quema!".to_string()));
+            }
+            self.hazard_tick += TICK;
+            if self.hazard_tick >= 0.5 {
+                p.damage(12);
+                ev.push(SimEvent::Hurt);
+                self.hazard_tick = 0.0;
+            }
+        } else {
+            self.hazard_tick = 0.0;
+        }
+        self.stats.damage += hp_before - p.hp;
+
+        // Muerte
+        if p.hp <= 0 {
+            ev.push(SimEvent::Died);
+            return;
+        }
+
+        // Sprites
+        self.sprites.update(TICK);
+
+        // Enemigos: patrullan, persiguen y pegan por contacto
+        let element = LEVELS[self.level].element;
+        let remaining = self.remaining();
+        let hp_before = self.player.hp;
+        let contact = self.enemies.update(TICK, &self.map, &mut self.player);
+        self.stats.damage += hp_before - self.player.hp;
+        match contact {
+            Contact::Hit => ev.push(SimEvent::Hurt),
+            // El líder solo pelea cuando el gimnasio está completo
+            Contact::Battle(i) if self.enemies.list[i].kind == EnemyKind::Leader => {
+                if remaining.unwrap_or(0) == 0 {
+                    let leader = LEVELS[self.level].leader;
+                    self.start_battle(BattleKind::Leader(leader), battle::leader_party(element), Some(i), ev);
+                } else {
+                    ev.push(SimEvent::Notice(format!("El líder no pelea: {}", faltan(remaining.unwrap_or(0)))));
+                }
+            }
+            Contact::Battle(i) => {
+                let foes = battle::trainer_party(element, &mut self.rng);
+                self.start_battle(BattleKind::Trainer, foes, Some(i), ev);
+            }
+            Contact::None => {}
+        }
+        if self.battle.is_some() { return; }
+
+        // Hierba alta: al entrar a una celda nueva, 1 en 8 de encuentro salvaje
+        if new_cell && self.map[ty][tx] == TILE_GRASS && rand_range(&mut self.rng, 8) == 0 {
+            let foe = battle::wild_creature(element, &mut self.rng);
+            self.start_battle(BattleKind::Wild, vec![foe], None, ev);
+            return;
+        }
+
+        // La meta sola no gana (se gana con la medalla del líder): solo avisa qué falta
+        if new_cell && self.map[ty][tx] == TILE_EXIT {
+            ev.push(SimEvent::Notice(self.exit_blocked()));
+        }
+    }
+
+    fn start_battle(&mut self, kind: BattleKind, foes: Vec<battle::Creature>, enemy: Option<usize>, ev: &mut Vec<SimEvent>) {
+        let seed = rand_range(&mut self.rng, i32::MAX) as u64;
+        self.battle = Some(Battle::new(kind, foes, seed));
+        self.battle_enemy = enemy;
+        ev.push(SimEvent::BattleStart);
+    }
+
+    fn step_battle(&mut self, ti: &TickInput, ev: &mut Vec<SimEvent>) {
+        let Some(b) = self.battle.as_mut() else { return; };
+        let party = &mut self.player.party;
+        // Un paso por pulsación
+        if ti.pressed(Action::Up) { b.input(BattleInput::Up, party); }
+        if ti.pressed(Action::Down) { b.input(BattleInput::Down, party); }
+        if ti.pressed(Action::Confirm) { b.input(BattleInput::Confirm, party); }
+        if ti.pressed(Action::Cancel) { b.input(BattleInput::Back, party); }
+
+        // Resultado de vuelta a la exploración
+        let Some(outcome) = b.update(TICK) else { return; };
+        let leader = matches!(b.kind, BattleKind::Leader(_));
+        self.battle = None;
+        self.player.invuln = 1.0; // un respiro al volver
+        match outcome {
+            Outcome::Won => {
+                if let Some(i) = self.battle_enemy.take() { self.enemies.remove(i); }
+                // Vencer al líder cierra el gimnasio con su medalla
+                if leader {
+                    self.player.inventory.add(ItemKind::Badge);
+                    ev.push(SimEvent::Won);
+                } else {
+                    ev.push(SimEvent::BattleEnd);
+                }
+            }
+            Outcome::Fled => ev.push(SimEvent::BattleEnd),
+            Outcome::Lost => ev.push(SimEvent::Died),
+        }
+    }
+}
+
+// "falta 1 objeto" / "faltan 3 objetos"
+fn faltan(n: usize) -> String {
+    if n == 1 { "falta 1 objeto".to_string() } else { format!("faltan {n} objetos") }
+}
@@ -1,5 +1,8 @@
 use crate::constants::rgb;
 use image::GenericImageView;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use crate::items::{ItemKind, Pickup};
 
 pub struct SpriteFrame {
     pub w: usize,
@@ -20,7 +23,7 @@ impl SpriteFrame {
     }
 }
 
-fn load_frame_rgba(path: &str) -> Option<SpriteFrame> {
+pub fn load_frame_rgba(path: &str) -> Option<SpriteFrame> {
     if let Ok(img) = image::open(path) {
         let img = img.to_rgba8();
         let (w, h) = img.dimensions();
@@ -77,47 +80,288 @@ fn gen_pokeball_frames(size: usize) -> Vec<SpriteFrame> {
     frames
 }
 
+// ===================== Sprite sheets =====================
+//
+// Formato del archivo de metadatos (`assets/<nombre>.sheet`), una directiva por línea:
+//
+//   image pokeball_sheet.png        # ruta relativa al .sheet
+//   anim  idle 0.15                 # nombre, segundos por frame (default)
+//   anim  die  0.10 once            # `once` = no hace loop (se queda en el último)
+//   frame idle 0 0  0 64 64         # anim, vista (0..7), x, y, w, h [duración]
+//
+// Las vistas van de 0 (de frente a la cámara) en sentido antihorario, cada 45°.
+// Una animación con una sola vista se ve igual desde cualquier lado.
+
+pub const VIEW_ANGLES: usize = 8;
+
+pub struct Animation {
+    pub name: String,
+    pub views: Vec<Vec<SpriteFrame>>, // [vista][frame]
+    pub durations: Vec<f64>,          // segundos por frame (compartido entre vistas)
+    pub looping: bool,
+}
+
+impl Animation {
+    /// Animación "idle" de una sola vista a `fps` constantes.
+    pub fn idle(frames: Vec<SpriteFrame>, fps: f64) -> Self {
+        let durations = vec![1.0 / fps.max(0.001); frames.len()];
+        Self { name: "idle".to_string(), views: vec![frames], durations, looping: true }
+    }
+
+    fn length(&self) -> f64 { self.durations.iter().sum::<f64>().max(1e-6) }
+
+    fn frame_index(&self, t: f64) -> usize {
+        let mut acc = 0.0;
+        for (i, d) in self.durations.iter().enumerate() {
+            acc += d;
+            if t < acc { return i; }
+        }
+        self.durations.len().saturating_sub(1)
+    }
+}
+
+/// Qué usar si no hay archivos en assets/.
+#[derive(Copy, Clone)]
+pub enum Fallback {
+    Frames(fn() -> Vec<SpriteFrame>, f64), // animación "idle" de una vista, a `fps`
+    Anims(fn() -> Vec<Animation>),         // hoja procedural completa (vistas, walk...)
+}
+
+pub struct SpriteSheet {
+    name: String,
+    fallback: Fallback,
+    image: Option<PathBuf>,
+    pub anims: Vec<Animation>,
+}
+
+fn rgba_from_image(img: &image::RgbaImage, x: u32, y: u32, w: u32, h: u32) -> SpriteFrame {
+    let (iw, ih) = img.dimensions();
+    let mut data = Vec::with_capacity((w * h) as usize);
+    for yy in y..y + h {
+        for xx in x..x + w {
+            if xx >= iw || yy >= ih { data.push(0); continue; }
+            let p = img.get_pixel(xx, yy);
+            data.push(((p[3] as u32) << 24) | ((p[0] as u32) << 16) | ((p[1] as u32) << 8) | p[2] as u32);
+        }
+    }
+    SpriteFrame { w: w.max(1) as usize, h: h.max(1) as usize, rgba: if data.is_empty() { vec![0] } else { data } }
+}
+
+// Animación a medio parsear: frames como (vista, frame, duración).
+struct AnimDef {
+    name: String,
+    dur: f64,
+    looping: bool,
+    frames: Vec<(usize, SpriteFrame, f64)>,
+}
+
+// Parsea un .sheet; devuelve (ruta de imagen, animaciones). None si está mal formado.
+fn parse_sheet(meta_path: &Path) -> Option<(PathBuf, Vec<Animation>)> {
+    let text = std::fs::read_to_string(meta_path).ok()?;
+    let dir = meta_path.parent().unwrap_or(Path::new("."));
+    let mut image_path = None;
+    let mut img = None;
+    let mut anims: Vec<AnimDef> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts.as_slice() {
+            ["image", file] => {
+                let p = dir.join(file);
+                img = Some(image::open(&p).ok()?.to_rgba8());
+                image_path = Some(p);
+            }
+            ["anim", name, dur, rest @ ..] => {
+                let looping = !rest.contains(&"once");
+                anims.push(AnimDef { name: name.to_string(), dur: dur.parse().ok()?, looping, frames: Vec::new() });
+            }
+            ["frame", name, view, x, y, w, h, rest @ ..] => {
+                let img = img.as_ref()?;
+                let anim = anims.iter_mut().find(|a| a.name == *name)?;
+                let view: usize = view.parse().ok()?;
+                let dur = match rest.first() { Some(d) => d.parse().ok()?, None => anim.dur };
+                let fr = rgba_from_image(img, x.parse().ok()?, y.parse().ok()?, w.parse().ok()?, h.parse().ok()?);
+                anim.frames.push((view.min(VIEW_ANGLES - 1), fr, dur));
+            }
+            [] => {}
+            _ => return None,
+        }
+    }
+
+    let mut out = Vec::new();
+    for AnimDef { name, looping, frames, .. } in anims {
+        let mut views: Vec<Vec<SpriteFrame>> = (0..VIEW_ANGLES).map(|_| Vec::new()).collect();
+        let mut durations = Vec::new();
+        for (view, fr, dur) in frames {
+            if view == 0 { durations.push(dur); }
+            views[view].push(fr);
+        }
+        if durations.is_empty() { continue; }
+        // Si no están las 8 vistas completas, la animación queda de una sola vista (la 0)
+        if views.iter().any(|v| v.is_empty()) { views.truncate(1); }
+        out.push(Animation { name, views, durations, looping });
+    }
+    if out.is_empty() { return None; }
+    Some((image_path?, out))
+}
+
+impl SpriteSheet {
+    /// Carga `assets/<name>.sheet`; si no existe prueba `assets/<name>_0.png`,
+    /// `_1.png`...; y si tampoco, usa lo procedural de `fallback`.
+    pub fn load(name: &str, fallback: Fallback) -> Self {
+        let meta = PathBuf::from(format!("assets/{name}.sheet"));
+        if let Some((image, anims)) = parse_sheet(&meta) {
+            return Self { name: name.to_string(), fallback, image: Some(image), anims };
+        }
+
+        let mut frames = Vec::new();
+        for i in 0.. {
+            match load_frame_rgba(&format!("assets/{name}_{i}.png")) {
+                Some(fr) => frames.push(fr),
+                None => break,
+            }
+        }
+        let anims = match fallback {
+            Fallback::Frames(make, fps) => {
+                if frames.is_empty() { frames = make(); }
+                vec![Animation::idle(frames, fps)]
+            }
+            Fallback::Anims(make) if frames.is_empty() => make(),
+            Fallback::Anims(_) => vec![Animation::idle(frames, 6.0)],
+        };
+        Self { name: name.to_string(), fallback, image: None, anims }
+    }
+
+    /// ¿El archivo cambiado alimenta esta hoja? (hot-reload)
+    pub fn uses(&self, path: &Path) -> bool {
+        if self.image.as_deref() == Some(path) { return true; }
+        let file = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        file == format!("{}.sheet", self.name)
+            || (file.starts_with(&format!("{}_", self.name)) && file.ends_with(".png"))
+    }
+
+    pub fn reloaded(&self) -> Self {
+        Self::load(&self.name, self.fallback)
+    }
+
+    pub fn anim_index(&self, name: &str) -> Option<usize> {
+        self.anims.iter().position(|a| a.name == name)
+    }
+}
+
 pub struct AnimatedSprite {
     pub x: f64,
     pub y: f64,
-    pub frames: Vec<SpriteFrame>,
-    pub fps: f64,
+    pub sheet: Rc<SpriteSheet>,
+    pub facing: f64, // radianes, hacia dónde "mira" el sprite en el mundo
+    pub scale: f64,  // alto en unidades de mundo (1.0 = alto de pared)
+    pub z: f64,      // altura de la base sobre el piso (flotantes, lámparas colgantes)
+    pub tint: u32,   // multiplicador RGB (0xFFFFFF = sin tinte)
+    pub alpha: u8,   // opacidad de todo el sprite (255 = opaco; el fantasma va translúcido)
+    anim: usize,
     t: f64,
 }
 
 impl AnimatedSprite {
-    pub fn new(x: f64, y: f64, frames: Vec<SpriteFrame>, fps: f64) -> Self {
-        Self { x, y, frames, fps, t: 0.0 }
+    pub fn new(x: f64, y: f64, sheet: Rc<SpriteSheet>) -> Self {
+        Self { x, y, sheet, facing: 0.0, scale: 1.0, z: 0.0, tint: 0xFF_FFFF, alpha: 255, anim: 0, t: 0.0 }
     }
+
+    /// Reemplaza la hoja (hot-reload) sin mover el sprite.
+    pub fn set_sheet(&mut self, sheet: Rc<SpriteSheet>) {
+        self.anim = self.anim.min(sheet.anims.len() - 1);
+        self.sheet = sheet;
+        self.t = 0.0;
+    }
+
+    /// Cambia de animación (idle, walk, attack, die...). Si no existe, se queda en la actual.
+    pub fn play(&mut self, name: &str) {
+        if let Some(i) = self.sheet.anim_index(name)
+            && i != self.anim
+        {
+            self.anim = i;
+            self.t = 0.0;
+        }
+    }
+
     pub fn update(&mut self, dt: f64) {
-        self.t = (self.t + dt * self.fps) % (self.frames.len() as f64);
+        let a = &self.sheet.anims[self.anim.min(self.sheet.anims.len() - 1)];
+        let len = a.length();
+        self.t += dt;
+        self.t = if a.looping { self.t % len } else { self.t.min(len - 1e-6) };
     }
-    pub fn current(&self) -> &SpriteFrame {
-        let i = self.t.floor() as usize % self.frames.len();
-        &self.frames[i]
+
+    /// Frame a dibujar visto desde la cámara en (cam_x, cam_y):
+    /// elige la vista según el ángulo entre el `facing` y la dirección a la cámara.
+    pub fn frame_for_view(&self, cam_x: f64, cam_y: f64) -> &SpriteFrame {
+        let a = &self.sheet.anims[self.anim.min(self.sheet.anims.len() - 1)];
+        let view = if a.views.len() == 1 { 0 } else {
+            let to_cam = (cam_y - self.y).atan2(cam_x - self.x);
+            let step = 2.0 * std::f64::consts::PI / VIEW_ANGLES as f64;
+            let rel = (to_cam - self.facing).rem_euclid(2.0 * std::f64::consts::PI);
+            ((rel / step).round() as usize) % VIEW_ANGLES
+        };
+        let frames = &a.views[view];
+        &frames[a.frame_index(self.t).min(frames.len() - 1)]
     }
 }
 
 pub struct SpriteManager {
-    pub list: Vec<AnimatedSprite>,
+    pub list: Vec<AnimatedSprite>, // decorativos
+    pub items: Vec<Pickup>,        // se recogen al caminar encima
 }
+
+pub fn pokeball_fallback() -> Vec<SpriteFrame> { gen_pokeball_frames(96) }
+
 impl SpriteManager {
     pub fn new_fire_gym() -> Self {
-        // Intenta cargar pokébola desde assets; si no, genera procedural.
-        let mut frames = Vec::new();
-        for i in 0..4 {
-            let path = format!("assets/pokeball_{}.png", i);
-            if let Some(fr) = load_frame_rgba(&path) { frames.push(fr); }
-        }
-        if frames.is_empty() {
-            frames = gen_pokeball_frames(96);
-        }
+        // Intenta cargar pokébola desde assets (sheet o PNGs numerados); si no, procedural.
+        let sheet = Rc::new(SpriteSheet::load("pokeball", Fallback::Frames(pokeball_fallback, 6.0)));
         // Colócala cerca de la meta (x≈21.5, y≈11.5)
-        let pokeball = AnimatedSprite::new(21.5, 11.5, frames, 6.0);
-        Self { list: vec![pokeball] }
+        let mut pokeball = AnimatedSprite::new(21.5, 11.5, sheet);
+        pokeball.play("idle"); // un .sheet puede declarar otras animaciones antes
+        pokeball.scale = 0.45;  // pokébola flotando a media altura
+        pokeball.z = 0.3;
+        Self { list: vec![pokeball], items: Vec::new() }
+    }
+
+    /// Sprites a dibujar este frame (decorativos + objetos).
+    pub fn visible(&self) -> impl Iterator<Item = &AnimatedSprite> {
+        self.list.iter().chain(self.items.iter().map(|i| &i.sprite))
+    }
+
+    /// Recoge los objetos a menos de `radius` del punto; devuelve sus tipos.
+    pub fn collect(&mut self, x: f64, y: f64, radius: f64) -> Vec<ItemKind> {
+        let mut got = Vec::new();
+        self.items.retain(|it| {
+            let near = (it.sprite.x - x).powi(2) + (it.sprite.y - y).powi(2) <= radius * radius;
+            if near { got.push(it.kind); }
+            !near
+        });
+        got
+    }
+
+    /// Recarga las hojas que usan `path`, sin mover los sprites (hot-reload).
+    pub fn reload(&mut self, path: &Path) {
+        let mut done: Vec<(Rc<SpriteSheet>, Rc<SpriteSheet>)> = Vec::new();
+        let sprites = self.list.iter_mut().chain(self.items.iter_mut().map(|i| &mut i.sprite));
+        for s in sprites {
+            if !s.sheet.uses(path) { continue; }
+            let new = match done.iter().find(|(old, _)| Rc::ptr_eq(old, &s.sheet)) {
+                Some((_, new)) => new.clone(),
+                None => {
+                    let new = Rc::new(s.sheet.reloaded());
+                    done.push((s.sheet.clone(), new.clone()));
+                    new
+                }
+            };
+            s.set_sheet(new);
+        }
     }
 
     pub fn update(&mut self, dt: f64) {
         for s in &mut self.list { s.update(dt); }
+        for it in &mut self.items { it.update(dt); }
     }
 }
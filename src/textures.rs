@@ -79,21 +79,56 @@ pub fn load_or_sky(path: &str, w: usize, h: usize) -> Texture {
     }
 }
 
+/// Puerta cerrada: tablones de madera con marco y cerradura dorada.
+fn gen_door(size: usize) -> Texture {
+    let mut data = vec![0; size*size];
+    for y in 0..size {
+        for x in 0..size {
+            let plank = (x * 5 / size).is_multiple_of(2);
+            let mut c = if plank { rgb(120, 72, 36) } else { rgb(104, 60, 30) };
+            if x < 3 || x >= size - 3 || y < 3 || y >= size - 3 { c = rgb(60, 60, 70); } // marco
+            if y % (size / 3) == size / 6 { c = rgb(70, 70, 80); }                          // bisagras
+            let (dx, dy) = (x as i32 - (size as i32 * 3 / 4), y as i32 - size as i32 / 2);
+            if dx*dx + dy*dy <= 9 { c = rgb(240, 200, 60); }                                 // cerradura
+            data[y*size + x] = c;
+        }
+    }
+    Texture { w: size, h: size, data }
+}
+
 pub struct TextureSet {
     pub wall_fire_a: Texture,
     pub wall_fire_b: Texture,
     pub sky: Texture,
+    pub door: Texture,
+    water: bool, // qué juego de archivos se usó (para recargar)
 }
 
+const FIRE_PATHS:  [&str; 3] = ["assets/fire_brick.png", "assets/magma.png", "assets/cielo.jpg"];
+const WATER_PATHS: [&str; 3] = ["assets/water_tiles.png", "assets/water_bricks.png", "assets/cielo_azul.jpg"];
+
 impl TextureSet {
     pub fn load() -> Self {
         Self {
-            wall_fire_a: load_or_fire("assets/fire_brick.png", 128, 128),
-            wall_fire_b: load_or_fire("assets/magma.png", 128, 128),
+            wall_fire_a: load_or_fire(FIRE_PATHS[0], 128, 128),
+            wall_fire_b: load_or_fire(FIRE_PATHS[1], 128, 128),
             // NUEVO: intenta cargar assets/cielo.jpg
-            sky: load_or_sky("assets/cielo.jpg", 1024, 256),
+            sky: load_or_sky(FIRE_PATHS[2], 1024, 256),
+            door: gen_door(64),
+            water: false,
         }
     }
+
+    /// ¿Este archivo alimenta alguna textura del set actual?
+    pub fn uses(&self, path: &std::path::Path) -> bool {
+        let paths = if self.water { &WATER_PATHS } else { &FIRE_PATHS };
+        paths.iter().any(|p| std::path::Path::new(p) == path)
+    }
+
+    /// Vuelve a leer todos los archivos del set (hot-reload en modo dev).
+    pub fn reload(&mut self) {
+        *self = if self.water { Self::load_water() } else { Self::load() };
+    }
 }
 
 // -------- Helpers de color --------
@@ -208,10 +243,12 @@ impl TextureSet {
     pub fn load_water() -> Self {
         Self {
             // Si existen, intenta cargar estos paths. Si no, usa el procedural azul.
-            wall_fire_a: load_or_water("assets/water_tiles.png", 128, 128),
-            wall_fire_b: load_or_water("assets/water_bricks.png", 128, 128),
+            wall_fire_a: load_or_water(WATER_PATHS[0], 128, 128),
+            wall_fire_b: load_or_water(WATER_PATHS[1], 128, 128),
             // Cielo más azul (o carga assets/cielo_azul.jpg)
-            sky: load_or_sky_blue("assets/cielo_azul.jpg", 1024, 256),
+            sky: load_or_sky_blue(WATER_PATHS[2], 1024, 256),
+            door: gen_door(64),
+            water: true,
         }
     }
 }
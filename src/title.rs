@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use crate::constants::{rgb, HEIGHT, WIDTH};
+use crate::hud::{draw_text, draw_text_centered, text_width};
+use crate::postfx::{self, Pass};
+use crate::replay::{Replay, REPLAY_PATH};
+use crate::sprites::{load_frame_rgba, SpriteFrame};
+
+pub const LOGO_PATH: &str = "assets/logo.png";
+pub const DEMO_DIR: &str = "assets/demos"; // repeticiones (.txt) para el modo demo
+const ATTRACT_AFTER: f64 = 12.0; // segundos sin tocar nada en el título hasta la demo
+pub const DEMO_MAX: f64 = 30.0;  // una demo se corta a los 30 s aunque la repetición siga
+const LOGO_DROP: f64 = 0.9;      // el logo cae desde arriba en este tiempo
+const PROMPT_AT: f64 = 1.2;      // y después aparece "presiona una tecla"
+
+/// Pantalla de título: fondo de lava (o agua) animado, logo que entra cayendo,
+/// "presiona una tecla" titilando y, si nadie toca nada, una demo con las
+/// repeticiones de `assets/demos` y la última partida (`replay.txt`).
+pub struct TitleScreen {
+    logo: Option<SpriteFrame>, // assets/logo.png; si no está, el título se escribe con la fuente
+    t: f64,                    // desde que se entró al título
+    water: bool,               // el fondo se alterna cada vez que se vuelve de una demo
+    next_demo: usize,
+}
+
+impl TitleScreen {
+    pub fn new() -> Self {
+        Self { logo: load_frame_rgba(LOGO_PATH), t: 0.0, water: false, next_demo: 0 }
+    }
+
+    pub fn uses(path: &Path) -> bool { path == Path::new(LOGO_PATH) }
+
+    pub fn reload(&mut self) { self.logo = load_frame_rgba(LOGO_PATH); }
+
+    /// Volver al título (desde el menú o una demo): la animación de entrada arranca de nuevo.
+    pub fn enter(&mut self, from_demo: bool) {
+        self.t = 0.0;
+        if from_demo { self.water = !self.water; }
+    }
+
+    /// Avanza el reloj; pasado un rato quieto devuelve la siguiente demo (si hay alguna).
+    pub fn update(&mut self, dt: f64) -> Option<Replay> {
+        self.t += dt;
+        if self.t < ATTRACT_AFTER { return None; }
+        self.t = 0.0;
+        let demos = demo_files();
+        for _ in 0..demos.len() {
+            let path = &demos[self.next_demo % demos.len()];
+            self.next_demo = (self.next_demo + 1) % demos.len();
+            match Replay::load(path) {
+                Some(r) if !r.ticks.is_empty() => return Some(r),
+                _ => eprintln!("{path}: no es una repetición válida, se saltea"),
+            }
+        }
+        None
+    }
+
+    pub fn draw(&self, buf: &mut [u32]) {
+        draw_liquid(buf, self.t, self.water);
+        let radius = HEIGHT as f32 / ((WIDTH * WIDTH + HEIGHT * HEIGHT) as f32).sqrt();
+        postfx::apply(buf, Pass::Vignette { color: rgb(8, 6, 6), inner: 0.0, slope: 0.6, max: 0.6, radius });
+
+        // Logo: cae desde arriba y rebota un poco al llegar
+        let k = (self.t / LOGO_DROP).min(1.0);
+        let back = 1.0 + 2.2 * (k - 1.0).powi(3) + 1.2 * (k - 1.0).powi(2); // "ease out back"
+        let target = HEIGHT as f64 * 0.28;
+        let cy = (target - (1.0 - back) * HEIGHT as f64 * 0.6) as i32;
+        match &self.logo {
+            Some(img) => draw_logo_image(buf, img, cy),
+            None => draw_logo_text(buf, cy),
+        }
+
+        // "Presiona una tecla": titila una vez por segundo
+        if self.t >= PROMPT_AT && (self.t - PROMPT_AT) % 1.0 < 0.65 {
+            let y = HEIGHT * 3 / 4;
+            draw_text_centered(buf, "PRESIONA CUALQUIER TECLA", y + 2, 3, rgb(0, 0, 0));
+            draw_text_centered(buf, "PRESIONA CUALQUIER TECLA", y, 3, rgb(255, 240, 200));
+        }
+        draw_text_centered(buf, "ESC SALIR", HEIGHT - 30, 2, rgb(200, 190, 180));
+    }
+}
+
+/// Cartel de la demo encima de la repetición que se está mostrando.
+pub fn draw_demo_banner(buf: &mut [u32], time: f64) {
+    postfx::apply(buf, Pass::Rect { x0: 0, y0: 24, x1: WIDTH, y1: 64, color: rgb(0, 0, 0), alpha: 0.45 });
+    draw_text_centered(buf, "DEMO", 30, 3, rgb(255, 210, 80));
+    if time % 1.0 < 0.65 {
+        draw_text_centered(buf, "presiona cualquier tecla", HEIGHT - 40, 2, rgb(240, 240, 240));
+    }
+}
+
+/// Fondo animado del título y del menú: ondas de lava (o de agua) con la fase `t`.
+pub fn draw_liquid(buf: &mut [u32], t: f64, water: bool) {
+    let t = t as f32;
+    // Paleta de 3 tonos: oscuro, medio y claro
+    let (deep, mid, light) = if water {
+        ((10u8, 30u8, 90u8), (30u8, 110u8, 200u8), (170u8, 230u8, 255u8))
+    } else {
+        ((110u8, 20u8, 10u8), (210u8, 60u8, 20u8), (255u8, 200u8, 60u8))
+    };
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let fx = x as f32 / WIDTH as f32;
+            let fy = y as f32 / HEIGHT as f32;
+
+            // Ondas entrecruzadas que se corren con el tiempo
+            let s1 = (fx * 11.0 + (fy * 4.0 + t * 0.7).sin() * 0.7 + t * 0.9).sin();
+            let s2 = (fy * 9.0  + (fx * 5.0 - t * 0.5).cos() * 0.6 - t * 0.6).cos();
+            let s3 = ((fx * 3.0 + fy * 6.0 + t * 0.3).sin()) * 0.5;
+            let v = ((s1 + s2 + s3) * 0.28 + 0.55).clamp(0.0, 1.0);
+
+            // 2 pasos de paleta para más contraste
+            let (r, g, b) = if v < 0.6 {
+                let t = (v / 0.6).clamp(0.0, 1.0);
+                (lerp_u8(deep.0, mid.0, t), lerp_u8(deep.1, mid.1, t), lerp_u8(deep.2, mid.2, t))
+            } else {
+                let t = ((v - 0.6) / 0.4).clamp(0.0, 1.0);
+                (lerp_u8(mid.0, light.0, t), lerp_u8(mid.1, light.1, t), lerp_u8(mid.2, light.2, t))
+            };
+
+            buf[y * WIDTH + x] = rgb(r, g, b);
+        }
+    }
+}
+
+#[inline]
+fn lerp_u8(a: u8, b: u8, t: f32) -> u8 {
+    let t = t.clamp(0.0, 1.0);
+    (a as f32 + (b as f32 - a as f32) * t).round().clamp(0.0, 255.0) as u8
+}
+
+// Repeticiones para la demo: las de assets/demos (en orden) y la última partida.
+fn demo_files() -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(DEMO_DIR).into_iter().flatten().flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "txt"))
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    if Path::new(REPLAY_PATH).exists() { files.push(REPLAY_PATH.to_string()); }
+    files
+}
+
+// Logo desde PNG (con alfa), achicado si no entra, centrado en `cy`.
+fn draw_logo_image(buf: &mut [u32], img: &SpriteFrame, cy: i32) {
+    let s = (WIDTH as f64 * 0.8 / img.w as f64).min(HEIGHT as f64 * 0.35 / img.h as f64).min(1.0);
+    let (w, h) = ((img.w as f64 * s) as i32, (img.h as f64 * s) as i32);
+    let (x0, y0) = ((WIDTH as i32 - w) / 2, cy - h / 2);
+    for y in 0..h {
+        let sy = y0 + y;
+        if sy < 0 || sy >= HEIGHT as i32 { continue; }
+        for x in 0..w {
+            let (c, a) = img.sample((x as f64 + 0.5) / w as f64, (y as f64 + 0.5) / h as f64);
+            if a < 8 { continue; }
+            let px = &mut buf[sy as usize * WIDTH + (x0 + x) as usize];
+            *px = if a == 255 { c } else { blend(*px, c, a as u32) };
+        }
+    }
+}
+
+// Sin logo.png: el nombre con la fuente, borde oscuro y sombra.
+fn draw_logo_text(buf: &mut [u32], cy: i32) {
+    let (title, scale) = ("POKE RAYCASTER", 7);
+    let x = (WIDTH as i32 - text_width(title, scale) as i32) / 2;
+    let y = cy - 4 * scale as i32;
+    draw_text(buf, title, x + 5, y + 6, scale, rgb(40, 10, 5));
+    for (dx, dy) in [(-2, 0), (2, 0), (0, -2), (0, 2)] {
+        draw_text(buf, title, x + dx, y + dy, scale, rgb(60, 20, 10));
+    }
+    draw_text(buf, title, x, y, scale, rgb(255, 215, 70));
+    let sub = "GIMNASIOS";
+    draw_text(buf, sub, (WIDTH as i32 - text_width(sub, 3) as i32) / 2, y + 10 * scale as i32, 3, rgb(255, 250, 235));
+}
+
+#[inline]
+fn blend(bg: u32, fg: u32, a: u32) -> u32 {
+    let mix = |s: u32| (((bg >> s) & 0xFF) * (255 - a) + ((fg >> s) & 0xFF) * a) / 255;
+    (mix(16) << 16) | (mix(8) << 8) | mix(0)
+}
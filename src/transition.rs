@@ -0,0 +1,92 @@
+use crate::constants::{HEIGHT, WIDTH};
+
+const DURATION: f64 = 0.6; // ida y vuelta (para la disolución: el cruce entero)
+const BLOCK: usize = 4;    // tamaño de los "píxeles" de la disolución
+
+/// Cómo se pasa de una pantalla a la otra.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    /// Se apaga a negro y se prende la otra.
+    Fade,
+    /// Un círculo se cierra sobre la vieja y se abre sobre la nueva.
+    Iris,
+    /// Bloques al azar van cambiando de una a otra (sin pasar por negro).
+    Dissolve,
+}
+
+/// Transición entre dos pantallas cualesquiera. Guarda el último frame de la
+/// que se va; la que llega se sigue dibujando normal y `draw` mezcla encima.
+/// Mientras está activa el juego ignora la entrada.
+pub struct Transition {
+    kind: Kind,
+    t: f64,
+    from: Vec<u32>,
+}
+
+impl Transition {
+    pub fn new() -> Self {
+        Self { kind: Kind::Fade, t: DURATION, from: Vec::new() }
+    }
+
+    /// Arranca desde lo que hay en `buf` (el último frame de la pantalla vieja).
+    pub fn start(&mut self, kind: Kind, buf: &[u32]) {
+        self.kind = kind;
+        self.t = 0.0;
+        self.from.clear();
+        self.from.extend_from_slice(buf);
+    }
+
+    pub fn active(&self) -> bool { self.t < DURATION }
+
+    /// Al final del frame, sobre la pantalla nueva ya dibujada.
+    pub fn draw(&mut self, buf: &mut [u32], dt: f64) {
+        if !self.active() { return; }
+        let p = self.t / DURATION;
+        self.t += dt;
+        // Primera mitad: la vieja se va; segunda: llega la nueva
+        let (out, k) = if p < 0.5 { (true, 1.0 - 2.0 * p) } else { (false, 2.0 * p - 1.0) };
+        if out && self.kind != Kind::Dissolve { buf.copy_from_slice(&self.from); }
+        match self.kind {
+            Kind::Fade => {
+                let k = (k * 256.0) as u32;
+                for px in buf.iter_mut() { *px = scale(*px, k); }
+            }
+            Kind::Iris => {
+                let (cx, cy) = (WIDTH as f64 / 2.0, HEIGHT as f64 / 2.0);
+                let r = cx.hypot(cy) * k;
+                let r2 = r * r;
+                for (y, row) in buf.chunks_mut(WIDTH).enumerate() {
+                    let dy = y as f64 - cy;
+                    if dy * dy > r2 { row.fill(0); continue; }
+                    let half = (r2 - dy * dy).sqrt();
+                    let (x0, x1) = ((cx - half).max(0.0) as usize, ((cx + half) as usize).min(WIDTH));
+                    row[..x0].fill(0);
+                    row[x1..].fill(0);
+                }
+            }
+            Kind::Dissolve => {
+                for (i, px) in buf.iter_mut().enumerate() {
+                    let (x, y) = (i % WIDTH / BLOCK, i / WIDTH / BLOCK);
+                    if noise(x, y) >= p { *px = self.from[i]; }
+                }
+            }
+        }
+    }
+}
+
+// Multiplica los tres canales por k/256.
+#[inline]
+fn scale(c: u32, k: u32) -> u32 {
+    let rb = (((c & 0xFF00FF) * k) >> 8) & 0xFF00FF;
+    let g = (((c & 0x00FF00) * k) >> 8) & 0x00FF00;
+    rb | g
+}
+
+// Valor fijo en 0..1 por bloque (hash entero), para el orden de la disolución.
+fn noise(x: usize, y: usize) -> f64 {
+    let mut h = (x as u32).wrapping_mul(0x9E37_79B1) ^ (y as u32).wrapping_mul(0x85EB_CA77);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2C1B_3C6D);
+    h ^= h >> 12;
+    (h & 0xFFFF) as f64 / 65536.0
+}
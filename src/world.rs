@@ -1,4 +1,4 @@
-use crate::constants::{MAP_H, MAP_W, TILE_EMPTY, TILE_EXIT, TILE_WALL, TILE_HAZARD};
+use crate::constants::{MAP_H, MAP_W, TILE_EMPTY, TILE_EXIT, TILE_WALL, TILE_HAZARD, TILE_DOOR, TILE_GRASS};
 
 pub type WorldMap = [[i32; MAP_W]; MAP_H];
 
@@ -91,8 +91,8 @@ pub fn gym_fuego() -> WorldMap {
     // Un único "puente" de entrada al centro del foso
     m[moat_y][(room_x0 - 2) as usize] = TILE_EMPTY;
 
-    // “Puerta” a la sala final: abre un hueco exacto en la pared izquierda
-    m[moat_y][room_x0 as usize] = TILE_EMPTY;
+    // “Puerta” a la sala final: hueco exacto en la pared izquierda, cerrado con llave
+    m[moat_y][room_x0 as usize] = TILE_DOOR;
 
     // ===================== 6) Colocación de la meta y entorno castigador =====================
     // Meta centrada en la sala y rodeada de lava (menos la casilla justo anterior)
@@ -124,10 +124,132 @@ pub fn gym_fuego() -> WorldMap {
     m
 }
 
+/// Carga un mapa de texto: una fila por línea, un dígito por celda
+/// (mismos ids que `TILE_*`). Lo que falte o no sea dígito queda como pared.
+/// Líneas que empiezan con `#` son comentarios.
+pub fn load_map_file(path: &str) -> Option<WorldMap> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let mut m = [[TILE_WALL; MAP_W]; MAP_H];
+    let rows = text.lines().filter(|l| !l.trim_start().starts_with('#'));
+    for (y, line) in rows.take(MAP_H).enumerate() {
+        for (x, ch) in line.chars().filter(|c| !c.is_whitespace()).take(MAP_W).enumerate() {
+            if let Some(d) = ch.to_digit(10) { m[y][x] = d as i32; }
+        }
+    }
+    Some(m)
+}
+
 #[inline]
 pub fn is_passable(tile: i32) -> bool {
-    // La lava es transitable (hace daño), paredes no. La salida también lo es.
-    tile == TILE_EMPTY || tile == TILE_EXIT || tile == TILE_HAZARD
+    // La lava es transitable (hace daño), paredes no. La salida y la hierba también.
+    tile == TILE_EMPTY || tile == TILE_EXIT || tile == TILE_HAZARD || tile == TILE_GRASS
+}
+
+/// Siembra manchones de hierba alta sobre piso vacío, lejos de (sx, sy).
+pub fn sprinkle_grass(m: &mut WorldMap, seed: u64, sx: usize, sy: usize) {
+    let mut s = (seed ^ 0x9E37_79B9_7F4A_7C15).rotate_left(13) | 1; // otra sal que la del rng de la partida
+    for _ in 0..4 {
+        let cx = 2 + rand_range(&mut s, MAP_W as i32 - 4) as usize;
+        let cy = 2 + rand_range(&mut s, MAP_H as i32 - 4) as usize;
+        if cx.abs_diff(sx) + cy.abs_diff(sy) < 5 { continue; }
+        let (x0, x1) = (cx.saturating_sub(2), (cx + 3).min(MAP_W - 1));
+        for row in &mut m[cy.saturating_sub(2)..(cy + 2).min(MAP_H - 1)] {
+            for t in &mut row[x0..x1] {
+                if *t == TILE_EMPTY && chance(&mut s, 3, 4) { *t = TILE_GRASS; }
+            }
+        }
+    }
+}
+
+/// Celdas alcanzables caminando desde (sx, sy) (BFS 4-vecinos sobre `is_passable`).
+pub fn reachable(map: &WorldMap, sx: usize, sy: usize) -> Vec<(usize, usize)> {
+    let mut seen = [[false; MAP_W]; MAP_H];
+    let mut out = Vec::new();
+    if sx >= MAP_W || sy >= MAP_H || !is_passable(map[sy][sx]) { return out; }
+    let mut queue = std::collections::VecDeque::from([(sx, sy)]);
+    seen[sy][sx] = true;
+    while let Some((x, y)) = queue.pop_front() {
+        out.push((x, y));
+        for (nx, ny) in [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)] {
+            if nx < MAP_W && ny < MAP_H && !seen[ny][nx] && is_passable(map[ny][nx]) {
+                seen[ny][nx] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    out
+}
+
+/// Línea de visión por DDA entre dos puntos de mundo: falsa si una celda
+/// no transitable (pared, puerta) se cruza en el camino.
+pub fn line_of_sight(map: &WorldMap, x0: f64, y0: f64, x1: f64, y1: f64) -> bool {
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let mut map_x = x0 as i32;
+    let mut map_y = y0 as i32;
+    let (end_x, end_y) = (x1 as i32, y1 as i32);
+
+    let delta_x = if dx == 0.0 { f64::INFINITY } else { (1.0 / dx).abs() };
+    let delta_y = if dy == 0.0 { f64::INFINITY } else { (1.0 / dy).abs() };
+    let (step_x, mut side_x) = if dx < 0.0 { (-1, (x0 - map_x as f64) * delta_x) }
+                               else { (1, (map_x as f64 + 1.0 - x0) * delta_x) };
+    let (step_y, mut side_y) = if dy < 0.0 { (-1, (y0 - map_y as f64) * delta_y) }
+                               else { (1, (map_y as f64 + 1.0 - y0) * delta_y) };
+
+    // t en [0,1] a lo largo del segmento; al pasar 1 llegamos al destino
+    while (map_x, map_y) != (end_x, end_y) {
+        if side_x < side_y {
+            if side_x > 1.0 { break; }
+            side_x += delta_x; map_x += step_x;
+        } else {
+            if side_y > 1.0 { break; }
+            side_y += delta_y; map_y += step_y;
+        }
+        if map_x < 0 || map_y < 0 || map_x >= MAP_W as i32 || map_y >= MAP_H as i32 { return false; }
+        if !is_passable(map[map_y as usize][map_x as usize]) { return false; }
+    }
+    true
+}
+
+/// A* sobre celdas transitables (4-vecinos). La lava cuesta más para que se
+/// prefiera rodearla. Devuelve el camino sin la celda inicial (vacío si ya estás).
+pub fn find_path(map: &WorldMap, from: (usize, usize), to: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    if to.0 >= MAP_W || to.1 >= MAP_H || !is_passable(map[to.1][to.0]) { return None; }
+    let h = |x: usize, y: usize| (x.abs_diff(to.0) + y.abs_diff(to.1)) as u32;
+
+    let mut cost = [[u32::MAX; MAP_W]; MAP_H];
+    let mut came: [[Option<(usize, usize)>; MAP_W]; MAP_H] = [[None; MAP_W]; MAP_H];
+    let mut open = BinaryHeap::new();
+    cost[from.1][from.0] = 0;
+    open.push(Reverse((h(from.0, from.1), from.0, from.1)));
+
+    while let Some(Reverse((_, x, y))) = open.pop() {
+        if (x, y) == to {
+            let mut path = Vec::new();
+            let mut cur = to;
+            while cur != from {
+                path.push(cur);
+                cur = came[cur.1][cur.0]?;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for (nx, ny) in [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)] {
+            if nx >= MAP_W || ny >= MAP_H { continue; }
+            let tile = map[ny][nx];
+            if !is_passable(tile) { continue; }
+            let step = if tile == TILE_HAZARD { 5 } else { 1 };
+            let c = cost[y][x] + step;
+            if c < cost[ny][nx] {
+                cost[ny][nx] = c;
+                came[ny][nx] = Some((x, y));
+                open.push(Reverse((c + h(nx, ny), nx, ny)));
+            }
+        }
+    }
+    None
 }
 
 #[inline]
@@ -139,7 +261,7 @@ fn rng_next(s: &mut u64) -> u64 {
     s.wrapping_mul(2685821657736338717)
 }
 #[inline]
-fn rand_range(s: &mut u64, max: i32) -> i32 {
+pub fn rand_range(s: &mut u64, max: i32) -> i32 {
     (rng_next(s) % (max as u64)) as i32
 }
 #[inline]
@@ -244,7 +366,7 @@ pub fn gym_agua(seed: u64) -> WorldMap {
         }
     }
     m[moat_y][(room_x0 - 2) as usize] = TILE_EMPTY;  // puente
-    m[moat_y][room_x0 as usize] = TILE_EMPTY;        // puerta
+    m[moat_y][room_x0 as usize] = TILE_DOOR;         // puerta (con llave)
 
     // Limpia interior
     for y in (room_y0 + 1) as usize..((room_y0 + goal_room_h - 1) as usize) {